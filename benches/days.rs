@@ -0,0 +1,90 @@
+//! Benchmarks for every day, on the real inputs.
+//!
+//! Run with `cargo bench`, or `cargo bench -- day05` for a single day. Days
+//! without an input file are skipped.
+
+use std::hint::black_box;
+
+use aoc2023::{
+    days::{day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11},
+    input::Source,
+    Solution,
+};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// Read the input for a day the same way the runner does
+fn load(day: u32) -> Option<String> {
+    match Source::resolve(day, None).read() {
+        Ok(input) => Some(input),
+        Err(e) => {
+            eprintln!("skipping day {}: {}", day, e);
+            None
+        }
+    }
+}
+
+/// Time parsing and both parts separately. The parts get an already parsed
+/// input, so they don't include the parse time.
+fn bench_day<S: Solution>(c: &mut Criterion, day: u32) {
+    let Some(input) = load(day) else { return };
+    let parsed = S::parse(&input).unwrap();
+
+    let mut group = c.benchmark_group(format!("day{:02}", day));
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&input))));
+    group.bench_function("part1", |b| b.iter(|| S::part1(black_box(&parsed))));
+    group.bench_function("part2", |b| b.iter(|| S::part2(black_box(&parsed))));
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<day01::Day01>(c, 1);
+    bench_day::<day02::Day02>(c, 2);
+    bench_day::<day03::Day03>(c, 3);
+    bench_day::<day04::Day04>(c, 4);
+    bench_day::<day05::Day05>(c, 5);
+    bench_day::<day06::Day06>(c, 6);
+    bench_day::<day07::Day07>(c, 7);
+    bench_day::<day08::Day08>(c, 8);
+    bench_day::<day09::Day09>(c, 9);
+    bench_day::<day10::Day10>(c, 10);
+    bench_day::<day11::Day11>(c, 11);
+}
+
+/// Day 5 part 2 can push the seed ranges through every category (`solve2`),
+/// collapse all categories into one map first (`solve2b`), or search back
+/// from the lowest locations.
+fn day05_strategies(c: &mut Criterion) {
+    let Some(input) = load(5) else { return };
+    let almanac = day05::parse_input(&input).unwrap();
+    let (seeds, categories) = (&almanac.seeds, &almanac.maps);
+
+    let mut group = c.benchmark_group("day05/part2");
+    group.bench_function("solve2 (ranges)", |b| {
+        b.iter(|| day05::lowest_location_ranges(black_box(seeds), black_box(categories)))
+    });
+    group.bench_function("solve2b (collapsed)", |b| {
+        b.iter(|| day05::lowest_location_collapsed(black_box(seeds), black_box(categories)))
+    });
+    group.bench_function("reverse", |b| {
+        b.iter(|| day05::lowest_location_reverse(black_box(seeds), black_box(categories)))
+    });
+    group.finish();
+}
+
+/// Day 8 follows a single ghost for part 1 (`get_steps`) and every ghost for
+/// part 2 (`get_better_steps`), both by finding their cycles and lining them
+/// up.
+fn day08_strategies(c: &mut Criterion) {
+    let Some(input) = load(8) else { return };
+    let map = input.parse::<day08::Map>().unwrap();
+
+    let mut group = c.benchmark_group("day08/steps");
+    group.bench_function("get_steps", |b| b.iter(|| black_box(&map).get_steps()));
+    group.bench_function("get_better_steps", |b| {
+        b.iter(|| black_box(&map).get_better_steps())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, days, day05_strategies, day08_strategies);
+criterion_main!(benches);
@@ -0,0 +1,242 @@
+use std::{collections::BTreeMap, fmt, fs, io, path::Path};
+
+use toml::{Table, Value};
+
+use crate::{runner::Part, Answer};
+
+/// The file with known-correct answers, relative to the repository root
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// The known answers for both parts of a single input
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Known {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Known {
+    fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+
+    fn get_mut(&mut self, part: Part) -> &mut Option<String> {
+        match part {
+            Part::One => &mut self.part1,
+            Part::Two => &mut self.part2,
+        }
+    }
+}
+
+/// How an answer compares to the known answer
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Pass => f.pad("pass"),
+            Verdict::Fail { .. } => f.pad("FAIL"),
+            Verdict::Unknown => f.pad("unknown"),
+        }
+    }
+}
+
+/// Known-correct answers, per day and input file.
+///
+/// Answers are stored as strings, so we don't care which integer type a day
+/// happens to return. In the file they look like:
+///
+/// ```toml
+/// [day01."input/day01.txt"]
+/// part1 = "52974"
+/// part2 = "53340"
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Answers {
+    days: BTreeMap<u32, BTreeMap<String, Known>>,
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io(io::Error),
+    Invalid(String),
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AnswersError::Io(e) => write!(f, "failed to access {}: {}", ANSWERS_FILE, e),
+            AnswersError::Invalid(e) => write!(f, "invalid {}: {}", ANSWERS_FILE, e),
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+impl Answers {
+    /// Load the answers from a file. A missing file just means we don't know
+    /// any answers yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<Answers, AnswersError> {
+        match fs::read_to_string(path) {
+            Ok(s) => s.parse(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(AnswersError::Io(e)),
+        }
+    }
+
+    /// Write the answers back to a file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), AnswersError> {
+        fs::write(path, self.to_string()).map_err(AnswersError::Io)
+    }
+
+    /// The known answer for a part of a day on an input
+    pub fn get(&self, day: u32, input: &str, part: Part) -> Option<&str> {
+        self.days.get(&day)?.get(input)?.get(part)
+    }
+
+    /// Compare an answer to the known answer
+    pub fn check(&self, day: u32, input: &str, part: Part, answer: &Answer) -> Verdict {
+        match self.get(day, input, part) {
+            Some(known) if known == answer.to_string() => Verdict::Pass,
+            Some(known) => Verdict::Fail {
+                expected: known.to_owned(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+
+    /// Record a confirmed answer, replacing whatever we knew before
+    pub fn record(&mut self, day: u32, input: &str, part: Part, answer: &Answer) {
+        *self
+            .days
+            .entry(day)
+            .or_default()
+            .entry(input.to_owned())
+            .or_default()
+            .get_mut(part) = Some(answer.to_string());
+    }
+}
+
+fn day_key(day: u32) -> String {
+    format!("day{:02}", day)
+}
+
+impl std::str::FromStr for Answers {
+    type Err = AnswersError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |e: String| AnswersError::Invalid(e);
+        let table = s
+            .parse::<Table>()
+            .map_err(|e| invalid(e.message().to_owned()))?;
+
+        let mut answers = Answers::default();
+        for (key, inputs) in table {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|d| d.parse::<u32>().ok())
+                .ok_or_else(|| invalid(format!("expected a day like 'day01', found '{}'", key)))?;
+            let inputs = match inputs {
+                Value::Table(inputs) => inputs,
+                _ => return Err(invalid(format!("'{}' should be a table of inputs", key))),
+            };
+
+            for (input, parts) in inputs {
+                let mut known = Known::default();
+                let parts = match parts {
+                    Value::Table(parts) => parts,
+                    _ => return Err(invalid(format!("'{}.{}' should be a table", key, input))),
+                };
+                for (part, answer) in parts {
+                    let slot = match part.as_str() {
+                        "part1" => &mut known.part1,
+                        "part2" => &mut known.part2,
+                        _ => return Err(invalid(format!("unknown part '{}' in {}", part, key))),
+                    };
+                    *slot = match answer {
+                        Value::String(s) => Some(s),
+                        Value::Integer(n) => Some(n.to_string()),
+                        _ => return Err(invalid(format!("answer for {} should be a string", key))),
+                    };
+                }
+                answers.days.entry(day).or_default().insert(input, known);
+            }
+        }
+        Ok(answers)
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut table = Table::new();
+        for (&day, inputs) in &self.days {
+            let mut day_table = Table::new();
+            for (input, known) in inputs {
+                let mut parts = Table::new();
+                for (key, answer) in [("part1", &known.part1), ("part2", &known.part2)] {
+                    if let Some(answer) = answer {
+                        parts.insert(key.into(), Value::String(answer.clone()));
+                    }
+                }
+                day_table.insert(input.clone(), Value::Table(parts));
+            }
+            table.insert(day_key(day), Value::Table(day_table));
+        }
+        write!(f, "{}", table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let mut answers = Answers::default();
+        answers.record(1, "input/day01.txt", Part::One, &Answer::U32(142));
+
+        let check = |part, answer| answers.check(1, "input/day01.txt", part, &Answer::U32(answer));
+        assert_eq!(check(Part::One, 142), Verdict::Pass);
+        assert_eq!(
+            check(Part::One, 143),
+            Verdict::Fail {
+                expected: "142".into()
+            }
+        );
+        assert_eq!(check(Part::Two, 281), Verdict::Unknown);
+        assert_eq!(
+            answers.check(1, "other.txt", Part::One, &Answer::U32(142)),
+            Verdict::Unknown
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut answers = Answers::default();
+        answers.record(1, "input/day01.txt", Part::One, &Answer::U32(142));
+        answers.record(1, "input/day01.txt", Part::Two, &Answer::U32(281));
+        answers.record(11, "input/day11.txt", Part::Two, &Answer::U64(82000210));
+
+        let text = answers.to_string();
+        assert!(text.contains("[day01.\"input/day01.txt\"]"));
+        assert_eq!(text.parse::<Answers>().unwrap(), answers);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!("[day01.\"a.txt\"]\npart3 = \"1\""
+            .parse::<Answers>()
+            .is_err());
+        assert!("[first.\"a.txt\"]\npart1 = \"1\""
+            .parse::<Answers>()
+            .is_err());
+        assert!("day01 = 5".parse::<Answers>().is_err());
+    }
+}
@@ -0,0 +1,297 @@
+use std::process::ExitCode;
+
+use aoc2023::{
+    answers::{Answers, Verdict, ANSWERS_FILE},
+    days::{day05, day08},
+    input::Source,
+    runner::{parse_days, run_day, Format, Part, PartResult},
+};
+
+const USAGE: &str = "Usage: aoc run <DAYS> [--part <1|2>] [--input <PATH>] [--format <FORMAT>]
+       aoc verify [DAYS] [--no-record]
+       aoc almanac collapse [--input <PATH>]
+       aoc network steps [--from <PATTERN>] [--to <PATTERN>] [--max-steps <N>] [--input <PATH>]
+
+DAYS can be a single day (5), an inclusive range (3..7) or 'all'.
+
+Inputs are read from --input (use '-' for stdin), otherwise from
+$AOC_INPUT_DIR/dayNN.txt, otherwise from input/dayNN.txt.
+
+FORMAT is text (the default), json or csv. json and csv have the parse and
+solve times in nanoseconds.
+
+verify checks the answers of all days (or DAYS) against answers.toml.
+Answers that aren't known yet are added to it, unless --no-record is given.
+
+almanac collapse composes all maps of the day 5 almanac into one, and
+writes the almanac with that single map.
+
+network steps counts the steps on the day 8 network until the ghosts that
+start on every node matching --from (AAA by default) are all on a node
+matching --to (ZZZ by default). A '*' in a pattern matches anything, so
+--from '*A' --to '*Z' is part 2. It gives up past --max-steps steps.";
+
+/// Errors in the arguments are followed by the usage, other errors are not
+enum CliError {
+    Usage(String),
+    Failed(String),
+}
+
+struct RunArgs {
+    days: Vec<u32>,
+    parts: Vec<Part>,
+    input: Option<String>,
+    format: Format,
+}
+
+fn parse_part(s: &str) -> Result<Part, String> {
+    match s {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(format!("invalid part '{}', expected 1 or 2", s)),
+    }
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs, String> {
+    let mut days = None;
+    let mut parts = Part::BOTH.to_vec();
+    let mut input = None;
+    let mut format = Format::Text;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => {
+                let part = args.next().ok_or("--part needs a value")?;
+                parts = vec![parse_part(part)?];
+            }
+            "--input" | "-i" => {
+                input = Some(args.next().ok_or("--input needs a value")?.clone());
+            }
+            "--format" | "-f" => {
+                format = args.next().ok_or("--format needs a value")?.parse()?;
+            }
+            _ if days.is_none() => days = Some(parse_days(arg)?),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let days = days.ok_or("no days given")?;
+    if input.is_some() && days.len() > 1 {
+        return Err("--input can only be used with a single day".into());
+    }
+
+    Ok(RunArgs {
+        days,
+        parts,
+        input,
+        format,
+    })
+}
+
+struct VerifyArgs {
+    days: Vec<u32>,
+    record: bool,
+}
+
+fn parse_verify_args(args: &[String]) -> Result<VerifyArgs, String> {
+    let mut days = None;
+    let mut record = true;
+
+    for arg in args {
+        match arg.as_str() {
+            "--no-record" => record = false,
+            _ if days.is_none() => days = Some(parse_days(arg)?),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let days = match days {
+        Some(days) => days,
+        None => parse_days("all")?,
+    };
+    Ok(VerifyArgs { days, record })
+}
+
+/// Read the input from a source and run the parts of a day on it
+fn run_source(day: u32, source: &Source, parts: &[Part]) -> Result<Vec<PartResult>, CliError> {
+    let input = source.read().map_err(|e| CliError::Failed(e.to_string()))?;
+    run_day(day, source, &input, parts)
+        .ok_or_else(|| CliError::Failed(format!("no solution for day {}", day)))?
+        .map_err(|e| CliError::Failed(format!("day {}: {}", day, e.render(&input))))
+}
+
+fn run(args: &[String]) -> Result<(), CliError> {
+    let args = parse_run_args(args).map_err(CliError::Usage)?;
+
+    let mut results = vec![];
+    for day in args.days {
+        let source = Source::resolve(day, args.input.as_deref());
+        results.extend(run_source(day, &source, &args.parts)?);
+    }
+
+    print!("{}", args.format.render(&results));
+    Ok(())
+}
+
+fn verify(args: &[String]) -> Result<(), CliError> {
+    let args = parse_verify_args(args).map_err(CliError::Usage)?;
+    let mut answers = Answers::load(ANSWERS_FILE).map_err(|e| CliError::Failed(e.to_string()))?;
+
+    println!(
+        "{:>3}  {:>4}  {:>16}  {:>16}  {:>7}  Input",
+        "Day", "Part", "Answer", "Expected", "Status"
+    );
+    let (mut failed, mut recorded) = (0, 0);
+    for day in args.days {
+        let source = Source::resolve(day, None);
+        let input = source.to_string();
+        for r in run_source(day, &source, &Part::BOTH)? {
+            let verdict = answers.check(day, &input, r.part, &r.answer);
+            let expected = match &verdict {
+                Verdict::Pass => r.answer.to_string(),
+                Verdict::Fail { expected } => expected.clone(),
+                Verdict::Unknown => "?".to_owned(),
+            };
+            println!(
+                "{:>3}  {:>4}  {:>16}  {:>16}  {:>7}  {}",
+                day, r.part, r.answer, expected, verdict, input
+            );
+
+            match verdict {
+                Verdict::Fail { .. } => failed += 1,
+                Verdict::Unknown if args.record => {
+                    answers.record(day, &input, r.part, &r.answer);
+                    recorded += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if recorded > 0 {
+        answers
+            .save(ANSWERS_FILE)
+            .map_err(|e| CliError::Failed(e.to_string()))?;
+        println!("\nrecorded {} new answer(s) in {}", recorded, ANSWERS_FILE);
+    }
+    if failed > 0 {
+        return Err(CliError::Failed(format!("{} answer(s) changed", failed)));
+    }
+    Ok(())
+}
+
+fn almanac(args: &[String]) -> Result<(), CliError> {
+    let (command, args) = args
+        .split_first()
+        .ok_or_else(|| CliError::Usage("almanac needs a command".into()))?;
+    if command != "collapse" {
+        return Err(CliError::Usage(format!(
+            "unknown almanac command '{}'",
+            command
+        )));
+    }
+
+    let path = match args {
+        [] => None,
+        [flag, path] if flag == "--input" || flag == "-i" => Some(path.as_str()),
+        _ => return Err(CliError::Usage("expected only --input <PATH>".into())),
+    };
+    let source = Source::resolve(5, path);
+    let input = source.read().map_err(|e| CliError::Failed(e.to_string()))?;
+    let almanac = day05::parse_input(&input)
+        .map_err(|e| CliError::Failed(format!("day 5: {}", e.render(&input))))?;
+
+    println!("{}", almanac.collapse());
+    Ok(())
+}
+
+struct StepsArgs {
+    from: String,
+    to: String,
+    max_steps: usize,
+    input: Option<String>,
+}
+
+fn parse_steps_args(args: &[String]) -> Result<StepsArgs, String> {
+    let mut steps = StepsArgs {
+        from: "AAA".into(),
+        to: "ZZZ".into(),
+        max_steps: day08::MAX_STEPS,
+        input: None,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--from" => steps.from = args.next().ok_or("--from needs a value")?.clone(),
+            "--to" => steps.to = args.next().ok_or("--to needs a value")?.clone(),
+            "--max-steps" => {
+                let max = args.next().ok_or("--max-steps needs a value")?;
+                steps.max_steps = max
+                    .parse()
+                    .map_err(|_| format!("invalid number of steps '{}'", max))?;
+            }
+            "--input" | "-i" => {
+                steps.input = Some(args.next().ok_or("--input needs a value")?.clone());
+            }
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    Ok(steps)
+}
+
+fn network(args: &[String]) -> Result<(), CliError> {
+    let (command, args) = args
+        .split_first()
+        .ok_or_else(|| CliError::Usage("network needs a command".into()))?;
+    if command != "steps" {
+        return Err(CliError::Usage(format!(
+            "unknown network command '{}'",
+            command
+        )));
+    }
+
+    let args = parse_steps_args(args).map_err(CliError::Usage)?;
+    let source = Source::resolve(8, args.input.as_deref());
+    let input = source.read().map_err(|e| CliError::Failed(e.to_string()))?;
+    let map = input
+        .parse::<day08::Map>()
+        .map_err(|e| CliError::Failed(format!("day 8: {}", e.render(&input))))?
+        .with_max_steps(args.max_steps);
+
+    let steps = map
+        .steps_matching(&args.from, &args.to)
+        .map_err(|e| CliError::Failed(e.to_string()))?;
+    println!("{}", steps);
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    let outcome = match args.first().map(|s| s.as_str()) {
+        Some("run") => run(&args[1..]),
+        Some("verify") => verify(&args[1..]),
+        Some("almanac") => almanac(&args[1..]),
+        Some("network") => network(&args[1..]),
+        Some("help") | Some("--help") | Some("-h") => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Some(cmd) => Err(CliError::Usage(format!("unknown command '{}'", cmd))),
+        None => Err(CliError::Usage("no command given".into())),
+    };
+
+    match outcome {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(CliError::Usage(e)) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            ExitCode::from(2)
+        }
+        Err(CliError::Failed(e)) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+use crate::{parse, Answer, ParseError, Solution, SolveError};
+
+/// The calibration value of a line, or None if it has no digits
+fn line_calibrate(line: &str) -> Option<u32> {
+    let mut digits = line.chars().filter_map(|c| c.to_digit(10));
+    // take the first digit, multiply by 10 and add the last digit.
+    let first = digits.next()?;
+    let last = digits.next_back().unwrap_or(first);
+    Some(first * 10 + last)
+}
+
+/// The calibration value of a line where digits can be spelled out, or None
+/// if it has no digits at all
+fn line_calibrate2(line: &str) -> Option<u32> {
+    let search = vec![
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+    let search_numbers = vec!["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+    let mut ranks: HashMap<usize, u32> = HashMap::new();
+    for (index, _) in line.char_indices() {
+        let str_part = &line[index..];
+        for (w_index, word) in search.iter().enumerate() {
+            if str_part.starts_with(*word) {
+                // get the corresponding number
+                ranks.insert(index, (w_index as u32) + 1);
+            }
+        }
+
+        for (w_index, word) in search_numbers.iter().enumerate() {
+            if str_part.starts_with(*word) {
+                // get the corresponding number
+                ranks.insert(index, w_index as u32);
+            }
+        }
+    }
+
+    // get the value with the lowest index
+    let lowest = ranks.iter().min_by_key(|&(i, _)| i)?.1;
+    // get the value with the highest index
+    let highest = ranks.iter().max_by_key(|&(i, _)| i)?.1;
+
+    // append the first digit to the last digit
+
+    Some(lowest * 10 + highest)
+}
+
+/// Every line needs a digit, either written as one or spelled out. Lines
+/// with only spelled out digits only work for part 2.
+pub fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    parse::lines(input, |line| match line_calibrate2(line) {
+        Some(_) => Ok(line.to_owned()),
+        None => Err(ParseError::new(line, line, "a digit")),
+    })
+}
+
+/// Add up the calibration values of the lines, or point out the first line
+/// that doesn't have one
+fn calibrate(lines: &[String], f: fn(&str) -> Option<u32>) -> Result<u32, SolveError> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            f(line).ok_or_else(|| SolveError::new(format!("line {} has no digits", i + 1)))
+        })
+        .sum()
+}
+
+pub fn solve(input: &str) -> u32 {
+    calibrate(&parse(input).unwrap(), line_calibrate).unwrap()
+}
+
+pub fn solve2(input: &str) -> u32 {
+    calibrate(&parse(input).unwrap(), line_calibrate2).unwrap()
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(calibrate(input, line_calibrate)?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(calibrate(input, line_calibrate2)?.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = r#"1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet"#;
+        let expected = 142;
+        assert_eq!(expected, solve(input));
+    }
+
+    #[test]
+    fn test_line_calibrate_1() {
+        let input = "1abc2";
+        let expected = Some(12);
+        assert_eq!(expected, line_calibrate(input));
+    }
+
+    #[test]
+    fn test_line_calibrate_2() {
+        let input = "xtwone3four";
+        let expected = Some(24);
+        assert_eq!(expected, line_calibrate2(input));
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = r#"two1nine
+        eightwothree
+        abcone2threexyz
+        xtwone3four
+        4nineeightseven2
+        zoneight234
+        7pqrstsixteen"#;
+        let expected = 281;
+
+        assert_eq!(expected, solve2(input));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse("1abc2\nabc\n3").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, "abc"));
+
+        // spelled out digits are fine for part 2, but not for part 1
+        let lines = parse("1abc2\ntwo€ne").unwrap();
+        assert_eq!(
+            Day01::part1(&lines),
+            Err(SolveError::new("line 2 has no digits"))
+        );
+        assert_eq!(Day01::part2(&lines), Ok(Answer::U32(12 + 22)));
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{parse, Answer, ParseError, Solution, SolveError};
+
+// The type reveal contains numbers of red green and blue cubes (R, G, B)
+pub type Reveal = (u32, u32, u32);
+
+/// The bag we're given in part 1: 12 red, 13 green and 14 blue cubes
+pub const BAG: Reveal = (12, 13, 14);
+
+#[derive(Debug, PartialEq)]
+pub struct Game {
+    nr: u32,
+    reveals: Vec<Reveal>,
+}
+
+impl Game {
+    pub fn is_solvable(&self, bag: Reveal) -> bool {
+        self.reveals
+            .iter()
+            .all(|reveal| self.is_solvable_reveal(reveal, bag))
+    }
+
+    pub fn is_solvable_reveal(&self, reveal: &Reveal, bag: Reveal) -> bool {
+        // check if the bag contains enough cubes
+        !(bag.0 < reveal.0 || bag.1 < reveal.1 || bag.2 < reveal.2)
+    }
+
+    pub fn power(&self) -> u32 {
+        let min_bag = self.get_min_bag();
+        min_bag.0 * min_bag.1 * min_bag.2
+    }
+
+    pub fn get_min_bag(&self) -> Reveal {
+        let mut min_bag = (0, 0, 0);
+        for reveal in &self.reveals {
+            min_bag.0 = min_bag.0.max(reveal.0);
+            min_bag.1 = min_bag.1.max(reveal.1);
+            min_bag.2 = min_bag.2.max(reveal.2);
+        }
+        min_bag
+    }
+}
+
+/// Parse the reveals of a game, like `3 blue, 4 red; 1 red, 2 green`.
+/// The whole line is passed along to point at the right place on errors.
+pub fn parse_reveals(line: &str, reveals_part: &str) -> Result<Vec<Reveal>, ParseError> {
+    let mut reveals: Vec<Reveal> = Vec::new();
+    for part in reveals_part.split(';') {
+        let mut r = 0;
+        let mut g = 0;
+        let mut b = 0;
+        for color in part.split(',') {
+            let color = color.trim();
+            // split the string into two parts by a space
+            let (number, color) = color
+                .split_once(' ')
+                .ok_or_else(|| ParseError::new(line, color, "a number and a color"))?;
+            let number = parse::number(line, number)?;
+            match color.trim() {
+                "red" => r = number,
+                "green" => g = number,
+                "blue" => b = number,
+                _ => return Err(ParseError::new(line, color, "red, green or blue")),
+            }
+        }
+        reveals.push((r, g, b));
+    }
+    Ok(reveals)
+}
+
+/// Parse a line into a game
+pub fn parse_game(line: &str) -> Result<Game, ParseError> {
+    let game = line.trim();
+    let (nr, reveals) = game
+        .split_once(':')
+        .ok_or_else(|| ParseError::end_of(line, "':'"))?;
+    // parse the game number
+    // split at space, take the second value
+    let nr = match nr.split_once(' ') {
+        Some(("Game", nr)) => parse::number(line, nr)?,
+        _ => return Err(ParseError::new(line, nr, "'Game <number>'")),
+    };
+    let reveals = parse_reveals(line, reveals)?;
+    Ok(Game { nr, reveals })
+}
+
+/// Parse the input into a vector of games
+pub fn get_games(input: &str) -> Result<Vec<Game>, ParseError> {
+    parse::lines(input, parse_game)
+}
+
+/// Sum the numbers of the games that are solvable with the given bag
+pub fn sum_solvable(games: &[Game], bag: Reveal) -> u32 {
+    games
+        .iter()
+        .filter(|game| game.is_solvable(bag))
+        .map(|game| game.nr)
+        .sum()
+}
+
+/// Sum the powers of the minimum bags of all games
+pub fn sum_powers(games: &[Game]) -> u32 {
+    games.iter().map(|game| game.power()).sum()
+}
+
+/// Return the solution for part 1 of the game
+pub fn solve(input: &str, bag: Reveal) -> u32 {
+    sum_solvable(&get_games(input).unwrap(), bag)
+}
+
+pub fn solve2(input: &str) -> u32 {
+    sum_powers(&get_games(input).unwrap())
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        get_games(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(sum_solvable(input, BAG).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(sum_powers(input).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = r#"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+        Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+        Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+        Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+        Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"#;
+        let expected = 8;
+        assert_eq!(expected, solve(input, (12, 13, 14)));
+    }
+
+    #[test]
+    fn test_parse_game() {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
+        let expected = Game {
+            nr: 1,
+            // R, G, B
+            reveals: vec![(4, 0, 3), (1, 2, 6), (0, 2, 0)],
+        };
+        assert_eq!(expected, parse_game(input).unwrap());
+    }
+
+    #[test]
+    fn test_parse_game_errors() {
+        let err = parse_game("Game 1: 3 blue, 4 purple").unwrap_err();
+        assert_eq!(err.column, 19);
+        assert_eq!(err.expected, "red, green or blue");
+
+        let err = parse_game("Game x: 3 blue").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (6, "x"));
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = r#"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+        Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+        Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+        Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+        Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"#;
+        let expected = 2286;
+        assert_eq!(expected, solve2(input));
+    }
+}
@@ -0,0 +1,306 @@
+use std::{collections::HashMap, str::FromStr};
+
+use crate::{grid::Grid, parse, Answer, ParseError, Solution, SolveError};
+
+/// A position on the schematic
+pub type Position = (usize, usize);
+
+/// A schematic of a machine
+#[derive(Debug)]
+pub struct Schematic {
+    numbers: HashMap<Position, u32>,
+    grid: Grid<char>,
+}
+
+impl Schematic {
+    /// Get all of the part numbers
+    pub fn part_numbers(&self) -> Vec<u32> {
+        let mut part_numbers = Vec::new();
+        for (position, number) in &self.numbers {
+            if self.is_adjacent(*position, number.to_string().len()) {
+                part_numbers.push(*number);
+            }
+        }
+        part_numbers
+    }
+
+    // returns true if the position is adjacent to a symbol
+    fn is_adjacent(&self, position: Position, length: usize) -> bool {
+        for offset in 0..=(length - 1) {
+            let (x, y) = position;
+            if self
+                .grid
+                .neighbours8((x + offset, y))
+                .any(|neighbour| self.symbol_at(neighbour).is_some())
+            {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Returns true if the number is adjacent to the position
+    fn nr_is_adjacent_to(
+        &self,
+        nr_position: Position,
+        nr_length: usize,
+        search_position: Position,
+    ) -> bool {
+        for offset in 0..=(nr_length - 1) {
+            let position = (nr_position.0 + offset, nr_position.1);
+            if self
+                .grid
+                .neighbours8(position)
+                .any(|p| p == search_position)
+            {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Get all of the 8 neighbours of a position
+    pub fn get_neighbours(&self, position: Position) -> Vec<Position> {
+        self.grid.neighbours8(position).collect()
+    }
+
+    /// Get the symbol at a position, if there is one. Anything that's not a
+    /// digit or a period is a symbol.
+    pub fn symbol_at(&self, position: Position) -> Option<char> {
+        self.grid
+            .get(position)
+            .copied()
+            .filter(|c| !c.is_ascii_digit() && *c != '.')
+    }
+
+    /// Get all of the gear ratios for the engine
+    pub fn gear_ratios(&self) -> Vec<u32> {
+        self.grid
+            .iter()
+            .filter(|(_, symbol)| **symbol == '*')
+            .filter_map(|(position, _)| self.get_gear_ratio(position))
+            .collect()
+    }
+
+    /// Get the gear ratio for a certain gear
+    /// (By position)
+    pub fn get_gear_ratio(&self, gear_pos: Position) -> Option<u32> {
+        // find two numbers that are both adjacent to the position
+        // first, find one number that is adjacent to the position
+        for (nr_pos, nr) in &self.numbers {
+            if self.nr_is_adjacent_to(*nr_pos, nr.to_string().len(), gear_pos) {
+                // find another number that is adjacent to the position
+                for (nr_pos2, nr2) in &self.numbers {
+                    // if number_position is the same as number_position2, skip
+                    if nr_pos == nr_pos2 {
+                        continue;
+                    }
+
+                    if self.nr_is_adjacent_to(*nr_pos2, nr2.to_string().len(), gear_pos) {
+                        return Some(nr * nr2);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+impl FromStr for Schematic {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.lines().next().is_none() {
+            return Err(ParseError::end_of(s, "a schematic"));
+        }
+        let mut numbers = HashMap::new();
+        for (y, line) in s.lines().enumerate() {
+            // numbers can be too big to fit, so we point at the whole number
+            // if parsing fails
+            let parse_number = |start: usize, end: usize| {
+                parse::number(line, &line[start..end]).map_err(|e| e.offset(y))
+            };
+            // where the number we're in started, as a column and as a byte
+            // offset into the line, which differ once there's a wide character
+            let mut start = None;
+            for (x, (i, c)) in line.char_indices().enumerate() {
+                match start {
+                    None if c.is_ascii_digit() => start = Some((x, i)),
+                    Some((column, from)) if !c.is_ascii_digit() => {
+                        numbers.insert((column, y), parse_number(from, i)?);
+                        start = None;
+                    }
+                    _ => {}
+                }
+            }
+            if let Some((column, from)) = start {
+                numbers.insert((column, y), parse_number(from, line.len())?);
+            }
+        }
+        let grid = s.parse::<Grid<char>>()?;
+        Ok(Schematic { numbers, grid })
+    }
+}
+
+/// Get the sum of all of the part numbers
+pub fn solve(input: &str) -> u32 {
+    let schem = Schematic::from_str(input).unwrap();
+    let part_numbers = schem.part_numbers();
+    part_numbers.iter().sum()
+}
+
+/// Get the sum of all of the gear ratios
+pub fn solve2(input: &str) -> u32 {
+    let schem = Schematic::from_str(input).unwrap();
+    let gear_ratios = schem.gear_ratios();
+    gear_ratios.iter().sum()
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Schematic;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Schematic::from_str(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(input.part_numbers().iter().sum::<u32>().into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(input.gear_ratios().iter().sum::<u32>().into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = r#"467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598.."#;
+        let expected = 4361;
+        assert_eq!(expected, solve(input));
+    }
+
+    #[test]
+    fn test_load_schematic() {
+        let input = r#"467..114..
+...*......
+..35..633."#;
+
+        let schematic = Schematic::from_str(input).unwrap();
+
+        println!("{:?}", schematic.numbers);
+
+        assert_eq!(schematic.numbers.len(), 4);
+
+        assert_eq!(schematic.numbers.get(&(0, 0)), Some(&467));
+        assert_eq!(schematic.numbers.get(&(5, 0)), Some(&114));
+        assert_eq!(schematic.numbers.get(&(2, 2)), Some(&35));
+        assert_eq!(schematic.numbers.get(&(6, 2)), Some(&633));
+
+        assert_eq!(schematic.symbol_at((3, 1)), Some('*'));
+        assert_eq!(schematic.symbol_at((2, 2)), None);
+    }
+
+    #[test]
+    fn test_numbers_connected() {
+        let input = r#"467..114..
+...*......
+..35..633."#;
+
+        let schematic = Schematic::from_str(input).unwrap();
+
+        let part_numbers = schematic.part_numbers();
+
+        assert_eq!(part_numbers.len(), 2);
+
+        // assert that part_numbers contains 467
+        assert!(part_numbers.contains(&467));
+        // assert that part_numbers contains 35
+        assert!(part_numbers.contains(&35));
+    }
+
+    #[test]
+    fn test_load_schematic_errors() {
+        let err = Schematic::from_str("").unwrap_err();
+        assert_eq!(err.expected, "a schematic");
+
+        let err = Schematic::from_str("...\n.99999999999.").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.text, "99999999999");
+
+        let err = Schematic::from_str("€..\n€99999999999").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.text, "99999999999");
+    }
+
+    #[test]
+    fn test_wide_characters() {
+        let schematic = Schematic::from_str("€12.\n..3€").unwrap();
+        assert_eq!(schematic.numbers.get(&(1, 0)), Some(&12));
+        assert_eq!(schematic.numbers.get(&(2, 1)), Some(&3));
+        assert_eq!(schematic.part_numbers().iter().sum::<u32>(), 15);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let input = r#"467..114..
+...*......
+..35..633."#;
+
+        let schematic = Schematic::from_str(input).unwrap();
+
+        let neighbours = schematic.get_neighbours((1, 1));
+
+        assert_eq!(neighbours.len(), 8);
+
+        // neighbours should contain (0, 0)
+        assert!(neighbours.contains(&(0, 0)));
+        // neighbours should contain (1, 0)
+        assert!(neighbours.contains(&(1, 0)));
+        // neighbours should contain (2, 0)
+        assert!(neighbours.contains(&(2, 0)));
+        // neighbours should contain (0, 1)
+        assert!(neighbours.contains(&(0, 1)));
+        // neighbours should contain (2, 1)
+        assert!(neighbours.contains(&(2, 1)));
+        // neighbours should contain (0, 2)
+        assert!(neighbours.contains(&(0, 2)));
+        // neighbours should contain (1, 2)
+        assert!(neighbours.contains(&(1, 2)));
+        // neighbours should contain (2, 2)
+        assert!(neighbours.contains(&(2, 2)));
+    }
+
+    #[test]
+    fn test_part_2() {
+        let input = r#"467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598.."#;
+        let expected = 467835;
+
+        let outcome = solve2(input);
+
+        assert_eq!(expected, outcome);
+    }
+}
@@ -0,0 +1,165 @@
+use std::str::FromStr;
+
+use crate::{parse, Answer, ParseError, Solution, SolveError};
+
+#[derive(Debug)]
+pub struct Card {
+    pub winners: Vec<u32>,
+    pub numbers: Vec<u32>,
+}
+
+impl Card {
+    /// Returns the number of matches between the winners and the numbers.
+    pub fn nr_matches(&self) -> u32 {
+        self.numbers
+            .iter()
+            .filter(|number| self.winners.contains(number))
+            .count() as u32
+    }
+
+    /// Returns the score of the card.
+    pub fn score(&self) -> u32 {
+        let score = self.nr_matches();
+
+        if score == 0 {
+            return 0;
+        }
+
+        2_u32.pow(score - 1)
+    }
+}
+
+impl FromStr for Card {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut winners = Vec::new();
+        let mut numbers = Vec::new();
+
+        let (card, numbers_part) = s
+            .split_once(" | ")
+            .ok_or_else(|| ParseError::end_of(s, "' | '"))?;
+        let (_, winners_part) = card
+            .split_once(": ")
+            .ok_or_else(|| ParseError::new(s, card, "'Card <number>: '"))?;
+
+        for winner in winners_part.trim().split(' ') {
+            // skip empty winners
+            if winner.is_empty() {
+                continue;
+            }
+            winners.push(parse::number(s, winner)?);
+        }
+
+        for number in numbers_part.trim().split(' ') {
+            // skip empty numbers
+            if number.is_empty() {
+                continue;
+            }
+            numbers.push(parse::number(s, number)?);
+        }
+
+        Ok(Card { winners, numbers })
+    }
+}
+
+/// Parse every line of the input into a card.
+pub fn parse_cards(input: &str) -> Result<Vec<Card>, ParseError> {
+    parse::lines(input, Card::from_str)
+}
+
+/// Returns the total score of all cards.
+pub fn total_score(cards: &[Card]) -> u32 {
+    cards.iter().map(|card| card.score()).sum()
+}
+
+/// Returns the number of cards we end up with, when every card wins copies
+/// of the cards below it.
+pub fn total_copies(cards: &[Card]) -> u32 {
+    let count = cards.len();
+    let mut copies = vec![1; count];
+
+    for (i, card) in cards.iter().enumerate() {
+        let nr_matches = card.nr_matches();
+        let from = i + 1;
+        let to = (i + nr_matches as usize + 1).min(count);
+        for j in from..to {
+            copies[j] += copies[i];
+        }
+    }
+
+    // return the sum of all the copies
+    copies.iter().sum()
+}
+
+/// Solve part 1 of the puzzle, calculate the score of the scratchcards.
+pub fn solve(input: &str) -> u32 {
+    total_score(&parse_cards(input).unwrap())
+}
+
+/// Solve part 2 of the puzzle, calculate the number of
+/// scratchcards based on the new rules.
+pub fn solve2(input: &str) -> u32 {
+    total_copies(&parse_cards(input).unwrap())
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<Card>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_cards(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(total_score(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(total_copies(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let input = r#"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"#;
+
+        let output = solve(input);
+
+        assert_eq!(output, 13);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse_cards("Card 1: 41 48 | 83 86\nCard 2: 13 32 | 6l 30").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 17));
+        assert_eq!(err.text, "6l");
+
+        let err = Card::from_str("Card 1: 41 48 83 86").unwrap_err();
+        assert_eq!(err.expected, "' | '");
+    }
+
+    #[test]
+    fn test_solve2() {
+        let input = r#"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"#;
+
+        let output = solve2(input);
+
+        assert_eq!(output, 30);
+    }
+}
@@ -0,0 +1,2551 @@
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::hash::Hash;
+use std::ops::{Add, Sub};
+use std::{ops::RangeInclusive, str::FromStr};
+
+use crate::{
+    parse,
+    ranges::{map_affine_into, Coalescer, Discrete, RangeSet},
+    Answer, ParseError, Solution, SolveError,
+};
+
+/// The unsigned integers an almanac can be read as. Values go from 0 up to
+/// and including `MAX`, so ranges include their end. Anything that would go
+/// past that is cut off rather than wrapped around.
+pub trait Unsigned:
+    Discrete + Hash + Debug + fmt::Display + FromStr + Add<Output = Self> + Sub<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const MAX: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn saturating_add(self, other: Self) -> Self;
+    fn saturating_sub(self, other: Self) -> Self;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {
+        $(impl Unsigned for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const MAX: Self = <$t>::MAX;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$t>::checked_sub(self, other)
+            }
+
+            fn saturating_add(self, other: Self) -> Self {
+                <$t>::saturating_add(self, other)
+            }
+
+            fn saturating_sub(self, other: Self) -> Self {
+                <$t>::saturating_sub(self, other)
+            }
+        })*
+    };
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+
+/// A range without any values in it. Every empty range is the same, it
+/// doesn't matter where it would have been.
+fn empty<T: Unsigned>() -> RangeInclusive<T> {
+    T::ONE..=T::ZERO
+}
+
+/// The range of `len` values starting at `start`, which has to fit
+fn span<T: Unsigned>(start: T, len: T) -> RangeInclusive<T> {
+    match len.checked_sub(T::ONE) {
+        Some(last) => start..=start + last,
+        None => empty(),
+    }
+}
+
+/// The number of values in a range that isn't empty, which has to fit
+fn count<T: Unsigned>(r: &RangeInclusive<T>) -> T {
+    *r.end() - *r.start() + T::ONE
+}
+
+/// Follow the output of translation a into translation b, returning the
+/// translations that take the inputs of a straight to the outputs of b, for
+/// the part of a's output that b handles.
+pub fn trans_shift_overlaps<T: Unsigned>(
+    a: &Translation<T>,
+    b: &Translation<T>,
+) -> Vec<Translation<T>> {
+    a.clip_to(&b.range())
+        .map(|t| Translation {
+            dst: b.translate(t.dst),
+            ..t
+        })
+        .into_iter()
+        .collect()
+}
+
+/// Subtract translation b from translation a looking at the outputs of a,
+/// returning the parts of a whose output b doesn't take as input.
+pub fn trans_sub_dst<T: Unsigned>(a: &Translation<T>, b: &Translation<T>) -> Vec<Translation<T>> {
+    RangeSet::from(a.out_range())
+        .difference(&b.range().into())
+        .shift(a.dst, a.src)
+        .ranges()
+        .iter()
+        .filter_map(|r| a.intersect(r))
+        .collect()
+}
+
+/// Subtract translation b from translation a looking at inputs, returning remaining parts.
+///
+/// # Examples
+///
+/// ```
+/// use aoc2023::days::day05::{trans_sub_src, Translation};
+///
+/// let a: Translation = Translation { src: 10, dst: 50, rng: 10 };
+/// let b = Translation { src: 15, dst: 70, rng: 5 };
+/// let c = trans_sub_src(&a, &b);
+/// assert_eq!(c, vec![Translation { src: 10, dst: 50, rng: 5 }]);
+/// ```
+///
+pub fn trans_sub_src<T: Unsigned>(a: &Translation<T>, b: &Translation<T>) -> Vec<Translation<T>> {
+    RangeSet::from(a.range())
+        .difference(&b.range().into())
+        .ranges()
+        .iter()
+        .filter_map(|r| a.intersect(r))
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Translation<T = u64> {
+    pub src: T,
+    pub dst: T,
+    pub rng: T,
+}
+
+/// Snipping more values off a translation than it has
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SnipError<T = u64> {
+    pub amount: T,
+    pub rng: T,
+}
+
+impl<T: fmt::Display> fmt::Display for SnipError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "can't snip {} values off a translation of {}",
+            self.amount, self.rng
+        )
+    }
+}
+
+impl<T: Debug + fmt::Display> std::error::Error for SnipError<T> {}
+
+impl<T: Unsigned> Translation<T> {
+    pub fn in_range(&self, value: T) -> bool {
+        value >= self.src && value - self.src < self.clamp().rng
+    }
+
+    pub fn translate(&self, value: T) -> T {
+        if self.in_range(value) {
+            self.dst + (value - self.src)
+        } else {
+            value
+        }
+    }
+
+    // start is inclusive, since we are using ranges
+    pub fn start(&self) -> T {
+        self.src
+    }
+
+    // the last value is inclusive too, so a translation can go right up to
+    // the largest value. Translations that would run past it (on either side)
+    // stop there, and empty translations don't have a last value.
+    pub fn last(&self) -> Option<T> {
+        let t = self.clamp();
+        t.rng.checked_sub(T::ONE).map(|n| t.src + n)
+    }
+
+    pub fn range(&self) -> RangeInclusive<T> {
+        span(self.src, self.clamp().rng)
+    }
+
+    pub fn out_range(&self) -> RangeInclusive<T> {
+        span(self.dst, self.clamp().rng)
+    }
+
+    /// A translation that leaves the values in `r` alone. There are more
+    /// values from 0 to the largest one than a translation can hold, so `r`
+    /// can't be all of them.
+    pub fn identity(r: RangeInclusive<T>) -> Translation<T> {
+        Translation {
+            src: *r.start(),
+            dst: *r.start(),
+            rng: if r.is_empty() { T::ZERO } else { count(&r) },
+        }
+    }
+
+    /// Cut off the part of the translation that would run past the largest
+    /// value, on either side
+    pub fn clamp(&self) -> Translation<T> {
+        // the most values that fit, that's one more than the room left after
+        // src or dst, unless that doesn't fit either
+        let room = (T::MAX - self.src).min(T::MAX - self.dst);
+        Translation {
+            rng: self.rng.min(room.saturating_add(T::ONE)),
+            ..*self
+        }
+    }
+
+    /// Split into the first `left` values and the rest. Either part can be
+    /// empty. An empty part past the largest value stays at the largest
+    /// value.
+    fn split_off(&self, left: T) -> Option<(Translation<T>, Translation<T>)> {
+        let t = self.clamp();
+        if left > t.rng {
+            return None;
+        }
+        Some((
+            Translation { rng: left, ..t },
+            Translation {
+                src: t.src.saturating_add(left),
+                dst: t.dst.saturating_add(left),
+                rng: t.rng - left,
+            },
+        ))
+    }
+
+    /// Split into the part before `at` and the part from `at` on. Either part
+    /// can be empty, but `at` has to be somewhere in `src..=src + rng`.
+    pub fn split_at(&self, at: T) -> Option<(Translation<T>, Translation<T>)> {
+        at.checked_sub(self.src)
+            .and_then(|left| self.split_off(left))
+    }
+
+    /// The part of this translation that handles the inputs in `r`, or None
+    /// if it doesn't handle any of them
+    pub fn intersect(&self, r: &RangeInclusive<T>) -> Option<Translation<T>> {
+        let t = self.clamp();
+        let src = t.range();
+        let start = *r.start().max(src.start());
+        let end = *r.end().min(src.end());
+        (start <= end).then(|| Translation {
+            src: start,
+            dst: t.dst + (start - t.src),
+            rng: count(&(start..=end)),
+        })
+    }
+
+    /// The part of this translation whose outputs land in `r`, or None if
+    /// none of them do
+    pub fn clip_to(&self, r: &RangeInclusive<T>) -> Option<Translation<T>> {
+        let t = self.clamp();
+        let dst = t.out_range();
+        let start = *r.start().max(dst.start());
+        let end = *r.end().min(dst.end());
+        (start <= end).then(|| Translation {
+            src: t.src + (start - t.dst),
+            dst: start,
+            rng: count(&(start..=end)),
+        })
+    }
+
+    /// Drop the first `amount` values
+    pub fn snip_left(&mut self, amount: T) -> Result<(), SnipError<T>> {
+        let (_, right) = self.split_off(amount).ok_or(SnipError {
+            amount,
+            rng: self.rng,
+        })?;
+        *self = right;
+        Ok(())
+    }
+
+    /// Drop the last `amount` values
+    pub fn snip_right(&mut self, amount: T) -> Result<(), SnipError<T>> {
+        let (left, _) = self
+            .rng
+            .checked_sub(amount)
+            .and_then(|keep| self.split_off(keep))
+            .ok_or(SnipError {
+                amount,
+                rng: self.rng,
+            })?;
+        *self = left;
+        Ok(())
+    }
+
+    // Translate a range, returning a tuple containing the translated ranges
+    // and the ranges that were not translated
+    pub fn translate_range(
+        &self,
+        r: &RangeInclusive<T>,
+    ) -> (Vec<RangeInclusive<T>>, Vec<RangeInclusive<T>>) {
+        let r = RangeSet::from(r.clone());
+        let t = self.clamp();
+        let src = RangeSet::from(t.range());
+        let translated = r.intersection(&src).shift(t.src, t.dst);
+        let not_translated = r.difference(&src);
+        (translated.into_ranges(), not_translated.into_ranges())
+    }
+}
+
+/// Write the translation the way the input does, as `dst src len`
+impl<T: fmt::Display> fmt::Display for Translation<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.dst, self.src, self.rng)
+    }
+}
+
+impl<T: Unsigned> FromStr for Translation<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut iter = s.split_whitespace();
+        let mut next = |expected| match iter.next() {
+            Some(token) => parse::number(s, token),
+            None => Err(ParseError::end_of(s, expected)),
+        };
+
+        let dst = next("a destination start")?;
+        let src = next("a source start")?;
+        let rng = next("a range length")?;
+
+        Ok(Translation { src, dst, rng })
+    }
+}
+
+/// A map from one category to the next.
+///
+/// The translations are kept sorted by source, without overlaps, and the gaps
+/// between them are filled with translations that leave values alone. That
+/// way every value from 0 up has exactly one segment, which we can find with
+/// a binary search. The translations as given are kept as well, so we can
+/// still tell what was wrong with them.
+#[derive(Debug, Clone)]
+pub struct Map<T = u64> {
+    source: String,
+    destination: String,
+    original: Vec<Translation<T>>,
+    segments: Vec<Translation<T>>,
+}
+
+/// Two maps are the same if they go between the same categories and move
+/// every value to the same place, however they were written down.
+impl<T: PartialEq> PartialEq for Map<T> {
+    fn eq(&self, other: &Map<T>) -> bool {
+        self.source == other.source
+            && self.destination == other.destination
+            && self.segments == other.segments
+    }
+}
+
+impl<T: Unsigned> Default for Map<T> {
+    fn default() -> Self {
+        Map::new(vec![])
+    }
+}
+
+impl<T: Unsigned> Map<T> {
+    /// Create a map from translations in any order. Where translations
+    /// overlap the first one wins, and anything past the largest value is
+    /// dropped.
+    pub fn new(translations: Vec<Translation<T>>) -> Map<T> {
+        let mut covered = RangeSet::new();
+        let mut pieces = vec![];
+        for t in translations
+            .iter()
+            .map(|t| t.clamp())
+            .filter(|t| t.rng > T::ZERO)
+        {
+            let free = RangeSet::from(t.range()).difference(&covered);
+            pieces.extend(free.ranges().iter().filter_map(|r| t.intersect(r)));
+            covered.insert(t.range());
+        }
+        pieces.sort_by_key(|t| t.src);
+
+        // fill up the gaps, and glue segments together that continue where
+        // the previous one stopped. A segment can't hold more than MAX values,
+        // whatever doesn't fit any more starts the next one, so maps that do
+        // the same thing always end up with the same segments.
+        let mut segments: Vec<Translation<T>> = Vec::with_capacity(pieces.len() * 2 + 2);
+        let mut push = |t: Translation<T>| {
+            let rest = match segments.last_mut() {
+                Some(last) if last.dst.checked_add(last.rng) == Some(t.dst) => {
+                    let glued = t.rng.min(T::MAX - last.rng);
+                    last.rng = last.rng + glued;
+                    t.split_off(glued).map(|(_, rest)| rest)
+                }
+                _ => Some(t),
+            };
+            segments.extend(rest.filter(|t| t.rng > T::ZERO));
+        };
+        // the first value that doesn't have a segment yet, None once we're
+        // past the largest value
+        let mut start = Some(T::ZERO);
+        for t in pieces {
+            if let Some(s) = start.filter(|&s| s < t.src) {
+                push(Translation::identity(s..=t.src - T::ONE));
+            }
+            start = t.last().and_then(Discrete::succ);
+            push(t);
+        }
+        match start {
+            // a single identity can't hold every value, so a map without
+            // translations gets two
+            Some(s) if s == T::ZERO => {
+                push(Translation::identity(T::ZERO..=T::MAX - T::ONE));
+                push(Translation::identity(T::MAX..=T::MAX));
+            }
+            Some(s) => push(Translation::identity(s..=T::MAX)),
+            None => {}
+        }
+
+        Map {
+            source: String::new(),
+            destination: String::new(),
+            original: translations,
+            segments,
+        }
+    }
+
+    /// Give the map the names of the categories it goes between
+    pub fn named(mut self, source: &str, destination: &str) -> Map<T> {
+        self.source = source.into();
+        self.destination = destination.into();
+        self
+    }
+
+    /// The category this map takes values from, like "seed"
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// The category this map takes values to, like "soil"
+    pub fn destination(&self) -> &str {
+        &self.destination
+    }
+
+    /// The translations the map was made from, as they were given
+    pub fn original(&self) -> &[Translation<T>] {
+        &self.original
+    }
+
+    /// All segments, including the ones that leave values alone
+    pub fn segments(&self) -> &[Translation<T>] {
+        &self.segments
+    }
+
+    /// The segments that actually move values
+    pub fn translations(&self) -> impl Iterator<Item = &Translation<T>> {
+        self.segments.iter().filter(|t| t.src != t.dst)
+    }
+
+    /// Find the segment that handles a value
+    fn segment(&self, value: T) -> Option<&Translation<T>> {
+        let i = self.segments.partition_point(|t| t.src <= value);
+        self.segments[..i].last().filter(|t| t.in_range(value))
+    }
+
+    pub fn translate(&self, value: T) -> T {
+        self.segment(value).map_or(value, |t| t.translate(value))
+    }
+
+    pub fn range_map(&self) -> HashMap<RangeInclusive<T>, RangeInclusive<T>> {
+        self.translations()
+            .map(|t| (t.range(), t.out_range()))
+            .collect()
+    }
+
+    /// The source ranges of the original translations that overlap, in
+    /// pairs. The first translation wins there, which might not be what the
+    /// input meant.
+    ///
+    /// Going through the ranges by where they start, every range that
+    /// overlaps an earlier one is reported once, together with the earlier
+    /// range that reaches furthest. Each pair is in input order, and so are
+    /// the pairs.
+    pub fn overlaps(&self) -> Vec<(RangeInclusive<T>, RangeInclusive<T>)> {
+        let mut ranges = self
+            .original
+            .iter()
+            .map(|t| t.range())
+            .enumerate()
+            .filter(|(_, r)| !r.is_empty())
+            .collect::<Vec<_>>();
+        ranges.sort_by_key(|(_, r)| *r.start());
+
+        let mut pairs = vec![];
+        let mut furthest: Option<&(usize, RangeInclusive<T>)> = None;
+        for current in &ranges {
+            match furthest {
+                Some(prev) if current.1.start() <= prev.1.end() => {
+                    pairs.push(if prev.0 < current.0 {
+                        (prev, current)
+                    } else {
+                        (current, prev)
+                    });
+                    if current.1.end() > prev.1.end() {
+                        furthest = Some(current);
+                    }
+                }
+                _ => furthest = Some(current),
+            }
+        }
+        pairs.sort_by_key(|(a, b)| (a.0, b.0));
+        pairs
+            .into_iter()
+            .map(|(a, b)| (a.1.clone(), b.1.clone()))
+            .collect()
+    }
+
+    /// Add another map to this map, so this map does what the other map does
+    /// after doing what it did before.
+    pub fn add_map(&mut self, other: &Map<T>) {
+        *self = self.compose(other);
+    }
+
+    /// Split a range of inputs into the parts that are translated the same
+    /// way, together with where the start of each part ends up
+    fn split(&self, r: &RangeInclusive<T>) -> Vec<(RangeInclusive<T>, T)> {
+        let mut parts = vec![];
+        if r.is_empty() {
+            return parts;
+        }
+        let mut start = *r.start();
+        let first = self
+            .segments
+            .partition_point(|t| t.last().is_some_and(|last| last < start));
+        for t in &self.segments[first..] {
+            let end = t.last().map_or(*r.end(), |last| last.min(*r.end()));
+            parts.push((start..=end, t.translate(start)));
+            match end.succ() {
+                Some(next) if next <= *r.end() => start = next,
+                _ => return parts,
+            }
+        }
+        parts.push((start..=*r.end(), start));
+        parts
+    }
+
+    /// Build the map that does what this map does, followed by what the other
+    /// map does.
+    pub fn compose(&self, other: &Map<T>) -> Map<T> {
+        let mut translations = vec![];
+        for t in &self.segments {
+            for (part, dst) in other.split(&t.out_range()) {
+                translations.push(Translation {
+                    src: t.src + (*part.start() - t.dst),
+                    dst,
+                    rng: count(&part),
+                });
+            }
+        }
+        Map::new(translations).named(&self.source, &other.destination)
+    }
+
+    /// The map that takes every output back to its input, or None if two
+    /// inputs end up at the same output.
+    pub fn inverse(&self) -> Option<Map<T>> {
+        let inputs = self
+            .translations()
+            .map(|t| t.range())
+            .collect::<RangeSet<_>>();
+        let outputs = self
+            .translations()
+            .map(|t| t.out_range())
+            .collect::<RangeSet<_>>();
+
+        // the translations have to land exactly on the inputs they take away.
+        // Anything else means some output is also reached by another
+        // translation, or by a value that stays where it is. (Overlapping
+        // outputs cover less than the inputs, so they can't be equal either.)
+        if inputs != outputs {
+            return None;
+        }
+
+        let translations = self
+            .translations()
+            .map(|t| Translation {
+                src: t.dst,
+                dst: t.src,
+                rng: t.rng,
+            })
+            .collect();
+        Some(Map::new(translations).named(&self.destination, &self.source))
+    }
+
+    /// Translate a set of values. The first translation that handles a value
+    /// wins, values that no translation handles stay the same.
+    pub fn translate_set(&self, set: &RangeSet<T>) -> RangeSet<T> {
+        let mut out = Coalescer::new();
+        self.translate_into(set.ranges(), &mut out);
+        out.into_set()
+    }
+
+    /// Translate ranges that are sorted by start and don't overlap, pushing
+    /// the translated pieces onto `out`
+    pub fn translate_into(&self, ranges: &[RangeInclusive<T>], out: &mut Coalescer<T>) {
+        let pieces = self.segments.iter().map(|t| (t.range(), t.dst));
+        map_affine_into(ranges, pieces, out);
+    }
+
+    pub fn translate_range(&self, r: &RangeInclusive<T>) -> Vec<RangeInclusive<T>> {
+        self.translate_set(&r.clone().into()).into_ranges()
+    }
+
+    /// This function takes a list of ranges and simplifies them
+    /// by merging overlapping ranges
+    /// and removing ranges that are fully contained in other ranges
+    pub fn simplify_ranges(ranges: Vec<RangeInclusive<T>>) -> Vec<RangeInclusive<T>> {
+        RangeSet::normalise(ranges)
+    }
+
+    /// All inputs that end up somewhere in `r`
+    pub fn preimage(&self, r: &RangeInclusive<T>) -> RangeSet<T> {
+        self.segments
+            .iter()
+            .filter_map(|t| t.clip_to(r))
+            .map(|t| t.range())
+            .collect()
+    }
+
+    /// All inputs that end up somewhere in the set
+    pub fn preimage_set(&self, set: &RangeSet<T>) -> RangeSet<T> {
+        set.ranges()
+            .iter()
+            .map(|r| self.preimage(r))
+            .fold(RangeSet::new(), |acc, p| acc.union(&p))
+    }
+
+    /// The lowest value the ranges are translated to, or None if the ranges
+    /// are all empty
+    pub fn lowest_in_ranges(&self, ranges: Vec<RangeInclusive<T>>) -> Option<T> {
+        let mut seeds = Coalescer::new();
+        seeds.extend(ranges);
+        let mut out = Coalescer::new();
+        self.translate_into(seeds.ranges(), &mut out);
+        out.min()
+    }
+}
+
+impl<T: Unsigned> FromStr for Map<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // the header with the category names is optional, so a map can also
+        // be just the translations
+        let header = s.lines().next().filter(|l| l.ends_with("map:"));
+        let names = header.map(parse_header).transpose()?;
+
+        let translations = s
+            .lines()
+            .enumerate()
+            .skip(usize::from(names.is_some()))
+            .map(|(i, l)| l.parse().map_err(|e: ParseError| e.offset(i)))
+            .collect::<Result<_, _>>()?;
+
+        let map = Map::new(translations);
+        Ok(match names {
+            Some((source, destination)) => map.named(source, destination),
+            None => map,
+        })
+    }
+}
+
+/// Write the map the way the input does. Only the translations that move
+/// values are written, sorted by source, so two maps that do the same thing
+/// come out the same.
+impl<T: Unsigned> fmt::Display for Map<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut lines = vec![];
+        if !self.source.is_empty() || !self.destination.is_empty() {
+            lines.push(format!("{}-to-{} map:", self.source, self.destination));
+        }
+        lines.extend(self.translations().map(|t| t.to_string()));
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Read the category names from a header like `seed-to-soil map:`
+fn parse_header(line: &str) -> Result<(&str, &str), ParseError> {
+    line.strip_suffix(" map:")
+        .and_then(|names| names.split_once("-to-"))
+        .filter(|(source, destination)| !source.is_empty() && !destination.is_empty())
+        .ok_or_else(|| ParseError::new(line, line, "'<source>-to-<destination> map:'"))
+}
+
+/// The puzzle input: the seeds, and the maps that take them from one category
+/// to the next
+#[derive(Debug, PartialEq, Clone)]
+pub struct Almanac<T = u64> {
+    pub seeds: Vec<T>,
+    pub maps: Vec<Map<T>>,
+}
+
+/// The path a value, or a set of values, takes through the categories
+#[derive(Debug, PartialEq, Clone)]
+pub struct Trace<T> {
+    pub steps: Vec<(String, T)>,
+}
+
+impl<T> Trace<T> {
+    /// Where the path ends up
+    pub fn last(&self) -> Option<&T> {
+        self.steps.last().map(|(_, v)| v)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Trace<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (category, value)) in self.steps.iter().enumerate() {
+            if i > 0 {
+                write!(f, " → ")?;
+            }
+            write!(f, "{} {}", category, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Something that's wrong with an almanac. The maps are numbered from 0.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Diagnostic<T = u64> {
+    /// Two translations in a map take some of the same values
+    Overlap {
+        map: usize,
+        first: RangeInclusive<T>,
+        second: RangeInclusive<T>,
+    },
+    /// A translation runs past the largest value
+    Overflow {
+        map: usize,
+        translation: Translation<T>,
+    },
+    /// A map doesn't start in the category the previous one ends in
+    BrokenChain {
+        map: usize,
+        expected: String,
+        found: String,
+    },
+    /// A seed range without any seeds in it
+    EmptySeedRange { start: T },
+    /// The last seed number has no length to go with it
+    UnpairedSeed { start: T },
+}
+
+impl<T: Unsigned> fmt::Display for Diagnostic<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Diagnostic::Overlap { map, first, second } => write!(
+                f,
+                "map {}: source ranges {:?} and {:?} overlap",
+                map, first, second
+            ),
+            Diagnostic::Overflow {
+                map,
+                translation: t,
+            } => write!(
+                f,
+                "map {}: '{} {} {}' runs past {}",
+                map,
+                t.dst,
+                t.src,
+                t.rng,
+                T::MAX
+            ),
+            Diagnostic::BrokenChain {
+                map,
+                expected,
+                found,
+            } => write!(
+                f,
+                "map {}: starts at '{}', but the previous map ends at '{}'",
+                map, found, expected
+            ),
+            Diagnostic::EmptySeedRange { start } => {
+                write!(f, "the seed range starting at {} is empty", start)
+            }
+            Diagnostic::UnpairedSeed { start } => {
+                write!(f, "the seed range starting at {} has no length", start)
+            }
+        }
+    }
+}
+
+impl<T: Unsigned> Almanac<T> {
+    /// Look for anything that would make the answers wrong or make us panic
+    /// later on. An almanac without problems gives an empty list.
+    pub fn validate(&self) -> Vec<Diagnostic<T>> {
+        let mut diagnostics = vec![];
+
+        for pair in self.seeds.chunks(2) {
+            match *pair {
+                [start] => diagnostics.push(Diagnostic::UnpairedSeed { start }),
+                [start, len] if len == T::ZERO => {
+                    diagnostics.push(Diagnostic::EmptySeedRange { start })
+                }
+                _ => {}
+            }
+        }
+
+        for (i, map) in self.maps.iter().enumerate() {
+            // maps without a header can't be checked
+            if let Some(prev) = i.checked_sub(1).map(|p| &self.maps[p]) {
+                let (expected, found) = (prev.destination(), map.source());
+                if !expected.is_empty() && !found.is_empty() && expected != found {
+                    diagnostics.push(Diagnostic::BrokenChain {
+                        map: i,
+                        expected: expected.into(),
+                        found: found.into(),
+                    });
+                }
+            }
+
+            for (first, second) in map.overlaps() {
+                diagnostics.push(Diagnostic::Overlap {
+                    map: i,
+                    first,
+                    second,
+                });
+            }
+
+            for t in map.original() {
+                if t.clamp().rng < t.rng {
+                    diagnostics.push(Diagnostic::Overflow {
+                        map: i,
+                        translation: *t,
+                    });
+                }
+            }
+        }
+
+        diagnostics
+    }
+
+    /// The same almanac, with all maps composed into a single one
+    pub fn collapse(&self) -> Almanac<T> {
+        let map = match self.maps.split_first() {
+            Some((first, rest)) => rest.iter().fold(first.clone(), |map, c| map.compose(c)),
+            None => Map::default(),
+        };
+        Almanac {
+            seeds: self.seeds.clone(),
+            maps: vec![map],
+        }
+    }
+
+    /// The category we start in, "seed" unless the maps say otherwise
+    fn first_category(&self) -> String {
+        self.maps.first().map_or("seed", |m| m.source()).to_string()
+    }
+
+    /// Follow a seed through every map
+    pub fn trace(&self, seed: T) -> Trace<T> {
+        let mut steps = vec![(self.first_category(), seed)];
+        let mut value = seed;
+        for map in &self.maps {
+            value = map.translate(value);
+            steps.push((map.destination().to_string(), value));
+        }
+        Trace { steps }
+    }
+
+    /// Follow a range of seeds through every map. The range gets split up
+    /// along the way, so every step is a set of ranges.
+    pub fn trace_range(&self, seeds: RangeInclusive<T>) -> Trace<RangeSet<T>> {
+        let mut set = RangeSet::from(seeds);
+        let mut steps = vec![(self.first_category(), set.clone())];
+        for map in &self.maps {
+            set = map.translate_set(&set);
+            steps.push((map.destination().to_string(), set.clone()));
+        }
+        Trace { steps }
+    }
+
+    /// The seed range from part 2 that holds the seed with the lowest
+    /// location, together with that seed
+    pub fn winning_range(&self) -> Option<(RangeInclusive<T>, T)> {
+        let origin = lowest_location_reverse(&self.seeds, &self.maps)?;
+        seed_ranges(&self.seeds)
+            .into_iter()
+            .find(|r| r.contains(&origin.seed))
+            .map(|r| (r, origin.seed))
+    }
+}
+
+/// Write the almanac the way the input does
+impl<T: Unsigned> fmt::Display for Almanac<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let seeds = self.seeds.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        write!(f, "seeds: {}", seeds.join(" "))?;
+        for map in &self.maps {
+            write!(f, "\n\n{}", map)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Unsigned> FromStr for Almanac<T> {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut iter = input.split("\n\n");
+
+        // take the first line
+        let first = iter.next().unwrap_or_default();
+        let seeds = first
+            .strip_prefix("seeds: ")
+            .ok_or_else(|| ParseError::new(first, first, "'seeds: '"))?
+            .split_whitespace()
+            .map(|s| parse::number(first, s))
+            .collect::<Result<Vec<T>, _>>()?;
+
+        // keep track of where we are in the input, so errors in the maps point
+        // to the right line
+        let mut offset = first.lines().count() + 1;
+        let mut maps = vec![];
+        for block in iter {
+            maps.push(block.parse().map_err(|e: ParseError| e.offset(offset))?);
+            offset += block.lines().count() + 1;
+        }
+
+        Ok(Almanac { seeds, maps })
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Almanac, ParseError> {
+    input.parse()
+}
+
+/// Transform the seed numbers into ranges, taking them as (start, length)
+/// pairs. A seed without a length and a range without seeds are ignored, and
+/// ranges that would run past the largest value stop there.
+pub fn seed_ranges<T: Unsigned>(seeds: &[T]) -> Vec<RangeInclusive<T>> {
+    seeds
+        .chunks_exact(2)
+        .filter_map(|c| {
+            let last = c[1].checked_sub(T::ONE)?;
+            Some(c[0]..=c[0].saturating_add(last))
+        })
+        .collect()
+}
+
+/// Find the lowest location for the seeds, by following every seed through
+/// the categories. Without any seeds there's no lowest location.
+pub fn lowest_location<T: Unsigned>(seeds: &[T], categories: &[Map<T>]) -> Option<T> {
+    seeds
+        .iter()
+        .map(|s| categories.iter().fold(*s, |acc, c| c.translate(acc)))
+        .min()
+}
+
+/// Find the lowest location for the seed ranges, by pushing the ranges
+/// through every category. Without any seeds in the ranges there's no lowest
+/// location.
+pub fn lowest_location_ranges<T: Unsigned>(seeds: &[T], categories: &[Map<T>]) -> Option<T> {
+    // we only ever need the ranges of the current category and the next one,
+    // so the same two buffers are used all the way through
+    let mut current = Coalescer::new();
+    let mut next = Coalescer::new();
+    current.extend(seed_ranges(seeds));
+    for c in categories {
+        next.clear();
+        c.translate_into(current.ranges(), &mut next);
+        std::mem::swap(&mut current, &mut next);
+    }
+
+    current.min()
+}
+
+/// Find the lowest location for the seed ranges, by first collapsing all
+/// categories into a single map
+pub fn lowest_location_collapsed<T: Unsigned>(seeds: &[T], categories: &[Map<T>]) -> Option<T> {
+    let ranges = seed_ranges(seeds);
+
+    let basemap = categories
+        .iter()
+        .fold(Map::default(), |map, c| map.compose(c));
+
+    basemap.lowest_in_ranges(ranges)
+}
+
+/// The lowest location, and the seed it comes from
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Origin<T = u64> {
+    pub location: T,
+    pub seed: T,
+}
+
+/// Find the lowest location for the seed ranges by searching backwards. We
+/// walk through the location ranges the last category lays out, from low to
+/// high, and follow each one back to the seeds. The first one that reaches a
+/// seed has the answer.
+pub fn lowest_location_reverse<T: Unsigned>(
+    seeds: &[T],
+    categories: &[Map<T>],
+) -> Option<Origin<T>> {
+    let seeds = seed_ranges(seeds).into_iter().collect::<RangeSet<_>>();
+    let back = |set: RangeSet<T>| {
+        categories
+            .iter()
+            .rev()
+            .fold(set, |set, c| c.preimage_set(&set))
+    };
+    let forward = |set: RangeSet<T>| categories.iter().fold(set, |set, c| c.translate_set(&set));
+
+    let last = categories.last().cloned().unwrap_or_default();
+    let mut locations = last
+        .segments()
+        .iter()
+        .map(|t| t.out_range())
+        .collect::<Vec<_>>();
+    locations.sort_by_key(|r| *r.start());
+
+    let mut best: Option<Origin<T>> = None;
+    for r in locations {
+        // output ranges can overlap, so we only stop once we're past the best
+        // location so far
+        if best.is_some_and(|b| b.location < *r.start()) {
+            break;
+        }
+        let hits = back(r.into()).intersection(&seeds);
+        let Some(location) = forward(hits).min() else {
+            continue;
+        };
+        if best.is_none_or(|b| location < b.location) {
+            let seed = back((location..=location).into())
+                .intersection(&seeds)
+                .min()?;
+            best = Some(Origin { location, seed });
+        }
+    }
+    best
+}
+
+/// Solve part 2 in all the ways we know, and complain if they don't agree
+pub fn check_part2<T: Unsigned>(seeds: &[T], categories: &[Map<T>]) -> Result<Origin<T>, String> {
+    let origin = lowest_location_reverse(seeds, categories).ok_or("no seeds to search")?;
+    let ranges = lowest_location_ranges(seeds, categories).ok_or("solve2 found no seeds")?;
+    let collapsed = lowest_location_collapsed(seeds, categories).ok_or("solve2b found no seeds")?;
+
+    if ranges != collapsed || ranges != origin.location {
+        return Err(format!(
+            "solve2 found {}, solve2b found {}, the reverse search found {}",
+            ranges, collapsed, origin.location
+        ));
+    }
+    Ok(origin)
+}
+
+pub fn solve(input: &str) -> Result<u64, SolveError> {
+    let almanac = parse_input(input)?;
+    lowest_location(&almanac.seeds, &almanac.maps)
+        .ok_or_else(|| SolveError::new("there are no seeds"))
+}
+
+pub fn solve2(input: &str) -> Result<u64, SolveError> {
+    let almanac = parse_input(input)?;
+    lowest_location_ranges(&almanac.seeds, &almanac.maps)
+        .ok_or_else(|| SolveError::new("none of the seed ranges have any seeds"))
+}
+
+pub fn solve2b(input: &str) -> Result<u64, SolveError> {
+    let almanac = parse_input(input)?;
+    lowest_location_collapsed(&almanac.seeds, &almanac.maps)
+        .ok_or_else(|| SolveError::new("none of the seed ranges have any seeds"))
+}
+
+pub fn solve2_reverse(input: &str) -> Result<Origin, SolveError> {
+    let almanac = parse_input(input)?;
+    lowest_location_reverse(&almanac.seeds, &almanac.maps)
+        .ok_or_else(|| SolveError::new("none of the seed ranges have any seeds"))
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Almanac;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError> {
+        let location = lowest_location(&input.seeds, &input.maps)
+            .ok_or_else(|| SolveError::new("there are no seeds"))?;
+        Ok(location.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError> {
+        let location = lowest_location_collapsed(&input.seeds, &input.maps)
+            .ok_or_else(|| SolveError::new("none of the seed ranges have any seeds"))?;
+        Ok(location.into())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn assert_vec_eq<T: PartialEq + Debug>(a: Vec<T>, b: Vec<T>) {
+        assert!(b.iter().all(|x| a.contains(x)), "{:?} != {:?}", a, b);
+    }
+
+    /// Check that every value has exactly one segment: they start at 0, each
+    /// one carries on where the previous one stopped, and the last one ends
+    /// at the largest value
+    fn assert_contiguous(map: &Map) {
+        let mut next = Some(0);
+        for t in map.segments() {
+            assert_eq!(Some(t.src), next, "{:?}", map.segments());
+            next = t.last().and_then(Discrete::succ);
+        }
+        assert_eq!(None, next, "{:?}", map.segments());
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = r#"seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4"#;
+
+        let expected = 35;
+
+        let output = solve(input);
+
+        assert_eq!(output, Ok(expected));
+    }
+
+    #[test]
+    fn test_solve2() {
+        let input = r#"seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4"#;
+
+        let expected = 46;
+
+        let output = solve2b(input);
+
+        assert_eq!(output, Ok(expected));
+        assert_eq!(solve2(input), Ok(expected));
+
+        // the lowest location comes from seed 82
+        let origin = Origin {
+            location: 46,
+            seed: 82,
+        };
+        assert_eq!(solve2_reverse(input), Ok(origin));
+        let almanac = parse_input(input).unwrap();
+        assert_eq!(check_part2(&almanac.seeds, &almanac.maps), Ok(origin));
+        assert_eq!(almanac.validate(), vec![]);
+        assert_eq!(almanac.winning_range(), Some((79..=92, 82)));
+        assert_eq!(
+            almanac.trace(82).to_string(),
+            "seed 82 → soil 84 → fertilizer 84 → water 84 → light 77 → \
+             temperature 45 → humidity 46 → location 46"
+        );
+    }
+
+    #[test]
+    fn test_no_seeds() {
+        let maps = "\n\nseed-to-soil map:\n50 98 2";
+
+        // an empty range and an unpaired seed leave nothing to look for in
+        // part 2, but part 1 still has seeds
+        for (seeds, lowest) in [("seeds: 79 0", 0u64), ("seeds: 79", 79)] {
+            let almanac = Day05::parse(&format!("{}{}", seeds, maps)).unwrap();
+            assert_eq!(Day05::part1(&almanac), Ok(lowest.into()), "{}", seeds);
+            assert_eq!(
+                Day05::part2(&almanac),
+                Err(SolveError::new("none of the seed ranges have any seeds")),
+                "{}",
+                seeds
+            );
+            assert_eq!(lowest_location_ranges(&almanac.seeds, &almanac.maps), None);
+            assert!(check_part2(&almanac.seeds, &almanac.maps).is_err());
+        }
+    }
+
+    #[test]
+    fn test_trace() {
+        let almanac = parse_input(
+            "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15",
+        )
+        .unwrap();
+
+        let trace = almanac.trace(79);
+        assert_eq!(trace.to_string(), "seed 79 → soil 81 → fertilizer 81");
+        assert_eq!(trace.last(), Some(&81));
+
+        // 13 and 14 end up somewhere else than 15
+        let trace = almanac.trace_range(13..=15);
+        assert_eq!(
+            trace.to_string(),
+            "seed {13..=15} → soil {13..=15} → fertilizer {0..=0, 52..=53}"
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let input = "seeds: 79 14 55 13
+
+seed-to-soil map:
+52 50 48
+50 98 2
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15";
+        let almanac = parse_input(input).unwrap();
+        // the translations come out sorted by source, and 0 15 37 continues
+        // right into 37 52 2
+        let expected = input.replace("0 15 37\n37 52 2\n39 0 15", "39 0 15\n0 15 39");
+        assert_eq!(almanac.to_string(), expected);
+        assert_eq!(parse_input(&almanac.to_string()), Ok(almanac.clone()));
+
+        let collapsed = almanac.collapse();
+        assert_eq!(collapsed.maps.len(), 1);
+        assert!(collapsed.to_string().contains("seed-to-fertilizer map:\n"));
+        assert_eq!(parse_input(&collapsed.to_string()), Ok(collapsed.clone()));
+        for seed in 0..120 {
+            assert_eq!(collapsed.trace(seed).last(), almanac.trace(seed).last());
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let almanac = parse_input(
+            "seeds: 79 0 55
+
+seed-to-soil map:
+50 98 2
+52 50 49
+
+fertilizer-to-water map:
+0 18446744073709551610 10",
+        )
+        .unwrap();
+
+        assert_eq!(
+            almanac.validate(),
+            vec![
+                Diagnostic::EmptySeedRange { start: 79 },
+                Diagnostic::UnpairedSeed { start: 55 },
+                Diagnostic::Overlap {
+                    map: 0,
+                    first: 98..=99,
+                    second: 50..=98
+                },
+                Diagnostic::BrokenChain {
+                    map: 1,
+                    expected: "soil".into(),
+                    found: "fertilizer".into()
+                },
+                Diagnostic::Overflow {
+                    map: 1,
+                    translation: Translation {
+                        src: u64::MAX - 5,
+                        dst: 0,
+                        rng: 10
+                    }
+                },
+            ]
+        );
+        // the part that fits is still translated
+        assert_eq!(almanac.maps[1].translate(u64::MAX - 1), 4);
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let map: Map<u64> = Map::new(
+            [(10, 11), (0, 6), (15, 2), (5, 4), (30, 11), (40, 1)]
+                .into_iter()
+                .map(|(src, rng)| Translation { src, dst: 0, rng })
+                .collect(),
+        );
+        assert_eq!(
+            map.overlaps(),
+            vec![(10..=20, 15..=16), (0..=5, 5..=8), (30..=40, 40..=40)]
+        );
+        assert_eq!(Map::<u64>::new(vec![]).overlaps(), vec![]);
+    }
+
+    #[test]
+    fn test_split_and_clip() {
+        let t: Translation = Translation {
+            src: 10,
+            dst: 50,
+            rng: 10,
+        };
+        let (left, right) = t.split_at(15).unwrap();
+        assert_eq!((left.range(), left.dst), (10..=14, 50));
+        assert_eq!((right.range(), right.dst), (15..=19, 55));
+        // splitting at either end leaves an empty part
+        assert_eq!(t.split_at(20).map(|(_, r)| r.rng), Some(0));
+        assert_eq!(t.split_at(9), None);
+        assert_eq!(t.split_at(21), None);
+
+        assert_eq!(
+            t.intersect(&(15..=29)).map(|t| (t.range(), t.dst)),
+            Some((15..=19, 55))
+        );
+        assert_eq!(t.intersect(&(20..=29)), None);
+        assert_eq!(t.intersect(&empty()), None);
+        assert_eq!(
+            t.clip_to(&(0..=51)).map(|t| (t.range(), t.dst)),
+            Some((10..=11, 50))
+        );
+        assert_eq!(t.clip_to(&(60..=69)), None);
+
+        let mut s = t;
+        assert_eq!(s.snip_left(3), Ok(()));
+        assert_eq!(s.snip_right(2), Ok(()));
+        assert_eq!((s.range(), s.dst), (13..=17, 53));
+        assert_eq!(s.snip_left(6), Err(SnipError { amount: 6, rng: 5 }));
+        assert_eq!(s.snip_right(5), Ok(()));
+        assert_eq!(s.rng, 0);
+
+        // a translation that runs past u64::MAX is cut off instead of
+        // overflowing
+        let t: Translation = Translation {
+            src: u64::MAX - 5,
+            dst: 0,
+            rng: 10,
+        };
+        assert_eq!(t.range(), u64::MAX - 5..=u64::MAX);
+        assert_eq!(t.intersect(&(0..=u64::MAX)).map(|t| t.rng), Some(6));
+        assert!(t.split_at(u64::MAX).is_some());
+
+        // but one that stops right at u64::MAX is kept whole
+        let mut t: Translation = Translation {
+            src: u64::MAX - 5,
+            dst: 0,
+            rng: 6,
+        };
+        assert_eq!(t.clamp(), t);
+        assert_eq!(t.translate(u64::MAX), 5);
+        assert_eq!(t.last(), Some(u64::MAX));
+        assert_eq!(t.snip_left(6), Ok(()));
+        assert_eq!(t.range(), empty());
+    }
+
+    #[test]
+    fn test_largest_value() {
+        let input = format!(
+            "seeds: {} 1 {} 2\n\nseed-to-soil map:\n0 {} 6",
+            u64::MAX,
+            u64::MAX - 3,
+            u64::MAX - 5
+        );
+        let almanac = Day05::parse(&input).unwrap();
+        assert_eq!(almanac.validate(), vec![]);
+        assert_eq!(almanac.maps[0].translate(u64::MAX), 5);
+        assert_eq!(
+            seed_ranges(&almanac.seeds),
+            vec![u64::MAX..=u64::MAX, u64::MAX - 3..=u64::MAX - 2]
+        );
+
+        // seed 1 is the lowest in part 1, part 2 has seeds 2 and 3 moved
+        // down from the top
+        assert_eq!(Day05::part1(&almanac), Ok(1u64.into()));
+        assert_eq!(Day05::part2(&almanac), Ok(2u64.into()));
+        assert_eq!(
+            check_part2(&almanac.seeds, &almanac.maps),
+            Ok(Origin {
+                location: 2,
+                seed: u64::MAX - 3
+            })
+        );
+        assert_eq!(
+            almanac.trace_range(u64::MAX - 1..=u64::MAX).last(),
+            Some(&RangeSet::from(4..=5))
+        );
+    }
+
+    #[test]
+    fn test_preimage() {
+        let map = "50 98 2\n52 50 48".parse::<Map>().unwrap();
+        // 50 and 51 come from 98 and 99, 52 and 53 from 50 and 51 and 0..=49
+        // stays where it is
+        assert_eq!(map.preimage(&(50..=53)).ranges(), &[50..=51, 98..=99]);
+        assert_eq!(map.preimage(&(40..=50)).ranges(), &[40..=49, 98..=98]);
+        assert!(map.preimage(&empty()).is_empty());
+    }
+
+    #[test]
+    fn test_parse_maps() {
+        let input = r#"seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15"#;
+
+        let almanac = parse_input(input).unwrap();
+
+        let expected_seeds = vec![79, 14, 55, 13];
+
+        assert_eq!(almanac.seeds, expected_seeds);
+        let names = almanac
+            .maps
+            .iter()
+            .map(|m| (m.source(), m.destination()))
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec![("seed", "soil"), ("soil", "fertilizer")]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let input = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50
+
+soil-to-fertilizer map:
+0 15 37
+37 5x 2";
+
+        let err = parse_input(input).unwrap_err();
+        assert_eq!((err.line, err.column), (5, 6));
+        assert_eq!(err.expected, "a range length");
+
+        let err = parse_input(&input.replace("52 50\n", "52 50 48\n")).unwrap_err();
+        assert_eq!((err.line, err.column), (9, 4));
+        assert_eq!(err.text, "5x");
+
+        let err = parse_input("seed: 79 14").unwrap_err();
+        assert_eq!(err.expected, "'seeds: '");
+
+        let err = parse_input(
+            &input
+                .replace("soil-to-", "soil to ")
+                .replace("52 50\n", "52 50 48\n"),
+        )
+        .unwrap_err();
+        assert_eq!((err.line, err.column), (7, 1));
+        assert_eq!(err.expected, "'<source>-to-<destination> map:'");
+    }
+
+    #[test]
+    fn test_map_parsing() {
+        let input = r#"50 98 2"#;
+
+        let expected = Map::new(vec![Translation {
+            src: 98,
+            dst: 50,
+            rng: 2,
+        }]);
+
+        let output = input.parse::<Map>().unwrap();
+
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_map_segments() {
+        let map = "50 98 2\n52 50 48".parse::<Map>().unwrap();
+        let segments = map
+            .segments()
+            .iter()
+            .map(|t| (t.range(), t.dst))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            segments,
+            vec![
+                (0..=49, 0),
+                (50..=97, 52),
+                (98..=99, 50),
+                (100..=u64::MAX, 100)
+            ]
+        );
+        assert_contiguous(&map);
+    }
+
+    #[test]
+    fn test_map_translation() {
+        let input = r#"50 98 2"#;
+
+        let map = input.parse::<Map>().unwrap();
+
+        assert_eq!(map.translate(97), 97);
+        assert_eq!(map.translate(98), 50);
+        assert_eq!(map.translate(99), 51);
+        assert_eq!(map.translate(100), 100);
+    }
+
+    #[test]
+    fn test_range_translation() {
+        let input = r#"50 98 2"#;
+
+        let map = input.parse::<Map>().unwrap();
+
+        assert_vec_eq(map.translate_range(&(95..=96)), vec![(95..=96)]);
+        assert_vec_eq(map.translate_range(&(95..=98)), vec![(95..=97), (50..=50)]);
+        assert_vec_eq(map.translate_range(&(95..=99)), vec![(95..=97), (50..=51)]);
+        assert_vec_eq(
+            map.translate_range(&(95..=100)),
+            vec![(95..=97), (50..=51), (100..=100)],
+        );
+    }
+
+    #[test]
+    fn test_range_translation2() {
+        let input = r#"52 50 48"#;
+        let map = input.parse::<Map>().unwrap();
+
+        assert_vec_eq(map.translate_range(&(79..=92)), vec![(81..=94)]);
+    }
+
+    #[test]
+    fn test_range_translation3() {
+        let input = r#"100 5 5
+200 10 5"#;
+        let map = input.parse::<Map>().unwrap();
+
+        assert_vec_eq(map.translate_range(&(0..=9)), vec![(0..=4), (100..=104)]);
+
+        assert_vec_eq(
+            map.translate_range(&(0..=14)),
+            vec![(0..=4), (100..=104), (200..=204)],
+        );
+
+        assert_vec_eq(
+            map.translate_range(&(0..=19)),
+            vec![(0..=4), (100..=104), (200..=204), (15..=19)],
+        );
+    }
+
+    #[test]
+    fn test_map_normalization() {
+        let input = r#"100 5 5
+200 10 5"#;
+        let mut map = input.parse::<Map>().unwrap();
+
+        // The next approach is going to be to come up with some kind of
+        // map normalization or flattening.
+        // It would involve creating a matrix that can be used to represent
+        // the operations that the translations would do.
+        // If we're able to present all translations in the map as a matrix,
+        // we could probably much more easily figure out the lowest value in
+        // the matrix.
+        // For instance, the above map would be represented as:
+        // 5..=9 => 100..=104
+        // 10..=14 => 200..=204
+        let range_map = map.range_map();
+
+        // assert that the rangemap contains the expected values
+        assert_eq!(range_map.get(&(5..=9)), Some(&(100..=104)));
+        assert_eq!(range_map.get(&(10..=14)), Some(&(200..=204)));
+
+        //
+        // If we add another map that has the following translations:
+        let map2 = "0 102 2".parse::<Map>().unwrap();
+        map.add_map(&map2);
+
+        // It would change our representation to:
+        // 5..=6 => 100..=101
+        // 7..=8 => 0..=1
+        // 9..=9 => 104..=104
+        // 10..=14 => 200..=204
+        // 102..=103 => 0..=1
+        let range_map = map.range_map();
+
+        // assert that the rangemap contains the expected values
+        assert_eq!(range_map.get(&(5..=6)), Some(&(100..=101)));
+        assert_eq!(range_map.get(&(7..=8)), Some(&(0..=1)));
+        assert_eq!(range_map.get(&(9..=9)), Some(&(104..=104)));
+        assert_eq!(range_map.get(&(10..=14)), Some(&(200..=204)));
+        assert_eq!(range_map.get(&(102..=103)), Some(&(0..=1)));
+
+        //
+        // If we add another map that has the following translations:
+        let map3 = "30 203 5".parse::<Map>().unwrap();
+        map.add_map(&map3);
+
+        // It would change our representation to:
+        // 5..=6 => 100..=101
+        // 7..=8 => 0..=1
+        // 9..=9 => 104..=104
+        // 10..=12 => 200..=202
+        // 13..=14 => 30..=31
+        // 102..=103 => 0..=1
+        // 203..=207 => 30..=34
+        let range_map = map.range_map();
+
+        // assert that the rangemap contains the expected values
+        assert_eq!(range_map.get(&(5..=6)), Some(&(100..=101)));
+        assert_eq!(range_map.get(&(7..=8)), Some(&(0..=1)));
+        assert_eq!(range_map.get(&(9..=9)), Some(&(104..=104)));
+        assert_eq!(range_map.get(&(10..=12)), Some(&(200..=202)));
+        assert_eq!(range_map.get(&(13..=14)), Some(&(30..=31)));
+        assert_eq!(range_map.get(&(102..=103)), Some(&(0..=1)));
+        assert_eq!(range_map.get(&(203..=207)), Some(&(30..=34)));
+
+        // If we manage to implement this, than for each range of seeds we can
+        // look up the input items that are in that range, and then look at the
+        // lowest value in the matrix.
+
+        // For instance, for an input seed range of 4..=9, the lowest value in
+        // output would be 0 (for seed 7).
+        assert_eq!(
+            map.lowest_in_ranges(vec![4..=9]),
+            Some(0),
+            "lowest_in_ranges(4..=9)",
+        );
+
+        // For an input range of 14..=14, the lowest value in output would be 31
+        assert_eq!(
+            map.lowest_in_ranges(vec![14..=14]),
+            Some(31),
+            "lowest_in_ranges(14..=14)",
+        );
+
+        // for an input range of 80..=119, the lowest value in output would be 0
+        assert_eq!(
+            map.lowest_in_ranges(vec![80..=119]),
+            Some(0),
+            "lowest_in_ranges(80..=119)",
+        );
+
+        // for an input range of 200..=204, the lowest value in output would be 30
+        assert_eq!(
+            map.lowest_in_ranges(vec![200..=204]),
+            Some(30),
+            "lowest_in_ranges(200..=204)",
+        );
+
+        // In this case, the lowest value is 0, so we can just return that.
+        // Looking quite critically at this, it seems that we can just
+        // look at the last map,
+    }
+
+    #[test]
+    fn test_map_normalization_entirely_within() {
+        let mut map = "100 0 50".parse::<Map>().unwrap();
+
+        let map2 = "210 110 30".parse::<Map>().unwrap();
+
+        map.add_map(&map2);
+
+        let range_map = map.range_map();
+
+        // assert that the rangemap contains the expected values
+        assert_eq!(range_map.get(&(0..=9)), Some(&(100..=109)));
+        assert_eq!(range_map.get(&(10..=39)), Some(&(210..=239)));
+        assert_eq!(range_map.get(&(40..=49)), Some(&(140..=149)));
+    }
+
+    #[test]
+    fn test_map_normalization_right_side() {
+        let mut map = "39 15 15".parse::<Map>().unwrap();
+
+        let map2 = "4 15 37".parse::<Map>().unwrap();
+
+        map.add_map(&map2);
+
+        //       15       30
+        //       39       54
+        //       |--------|
+        //  15       52
+        //  4        41
+        //  |--------|
+        let range_map = map.range_map();
+
+        // assert that the rangemap contains the expected values
+        assert_eq!(range_map.get(&(28..=29)), Some(&(52..=53)));
+        assert_eq!(range_map.get(&(15..=27)), Some(&(28..=40)));
+    }
+
+    #[test]
+    fn test_map_normalization_right_side2() {
+        let mut map = "39 0 14".parse::<Map>().unwrap();
+
+        let map2 = "3 14 38".parse::<Map>().unwrap();
+
+        map.add_map(&map2);
+
+        //       0        14
+        //       39       53
+        //       |--------|
+        //  14       52
+        //  3        41
+        //  |--------|
+        let range_map = map.range_map();
+
+        // assert that the rangemap contains the expected values
+        assert_eq!(range_map.get(&(13..=13)), Some(&(52..=52)));
+        assert_eq!(range_map.get(&(0..=12)), Some(&(28..=40)));
+    }
+
+    #[test]
+    fn test_overlap_check() {
+        let mut map = "50 98 2
+52 50 48"
+            .parse::<Map>()
+            .unwrap();
+
+        // the map should have only 2 translations!
+        assert_eq!(map.translations().count(), 2);
+
+        let map2 = "0 15 37
+        37 52 2
+        39 0 15"
+            .parse::<Map>()
+            .unwrap();
+
+        map.add_map(&map2);
+
+        assert_contiguous(&map);
+    }
+
+    #[test]
+    fn test_overlap_check2() {
+        let mut map = "52 50 48".parse::<Map>().unwrap();
+
+        let map2 = "37 52 2".parse::<Map>().unwrap();
+
+        // 50       98
+        // 52       100
+        // |--------|
+        // 52   54
+        // 37   39
+        // |----|
+
+        // resulting map should be
+        // 50..=51 => 37..=38
+        // 52..=97 => 54..=99
+
+        map.add_map(&map2);
+
+        assert_contiguous(&map);
+    }
+
+    #[test]
+    fn test_overlap_check3() {
+        let mut map = "50 98 2
+ 52 50 48
+ 0 15 35
+ 39 0 15"
+            .parse::<Map>()
+            .unwrap();
+
+        let map2 = "0 11 42".parse::<Map>().unwrap();
+
+        // 0    15 15      50 50       98 98       100
+        // 39   54 0       35 52      100 50       52
+        // |----|  |-------|  |-------|   |--------|
+        // 11..=52 => 0..=41
+        //
+
+        map.add_map(&map2);
+
+        assert_contiguous(&map);
+    }
+
+    #[test]
+    fn test_overlap_check4() {
+        let mut map = "0 15 35".parse::<Map>().unwrap();
+
+        let map2 = "0 11 42".parse::<Map>().unwrap();
+
+        map.add_map(&map2);
+
+        //     15      50
+        //     0       35
+        //     |-------|
+        // 11..=52 => 0..=41
+        // 11  15 15  26 26   50 50    53
+        // 0   4  0   11 0    24 39    42
+        // |---|  |---|  |----|  |-----|
+
+        assert_contiguous(&map);
+    }
+
+    #[test]
+    fn test_overlap_check5() {
+        let mut map = "0 15 35
+39 0 15"
+            .parse::<Map>()
+            .unwrap();
+
+        let map2 = "0 11 42".parse::<Map>().unwrap();
+
+        map.add_map(&map2);
+
+        //  0        15
+        //  39       54
+        //  |--------|
+        //     50  53
+
+        assert_contiguous(&map);
+    }
+
+    #[test]
+    fn test_overlap_check6() {
+        let mut map = "52 50 48".parse::<Map>().unwrap();
+
+        let map2 = "49 53 8
+0 11 42"
+            .parse::<Map>()
+            .unwrap();
+
+        map.add_map(&map2);
+
+        //  50           98
+        //  52           100
+        //  |------------|
+        //    53 61
+        //    49 57
+        //    |---|
+        assert_contiguous(&map);
+    }
+
+    #[test]
+    fn test_add_map_to_empty_map() {
+        let mut map = Map::default();
+
+        let map2 = "50 98 2
+52 50 48"
+            .parse::<Map>()
+            .unwrap();
+
+        map.add_map(&map2);
+
+        // the map should have only 2 translations!
+        assert_eq!(map.translations().count(), 2);
+    }
+
+    #[test]
+    fn test_translation_impl() {
+        let t: Translation = Translation {
+            src: 98,
+            dst: 50,
+            rng: 2,
+        };
+
+        assert!(!t.in_range(97));
+        assert!(t.in_range(98));
+        assert!(t.in_range(99));
+        assert!(!t.in_range(100));
+
+        assert_eq!(t.start(), 98);
+        assert_eq!(t.last(), Some(99));
+    }
+
+    #[test]
+    fn test_simplify_ranges() {
+        let input = vec![(0..=9), (5..=14), (20..=29), (25..=34)];
+
+        let expected = vec![(0..=14), (20..=34)];
+
+        let output = Map::<u64>::simplify_ranges(input);
+
+        assert_vec_eq(output, expected);
+    }
+
+    #[test]
+    fn test_trans_sub_src_cover() {
+        let a: Translation = Translation {
+            src: 10,
+            dst: 50,
+            rng: 10,
+        };
+        let b = Translation {
+            src: 5,
+            dst: 70,
+            rng: 20,
+        };
+        let c = trans_sub_src(&a, &b);
+        assert_eq!(c, vec![]);
+    }
+
+    #[test]
+    fn test_trans_sub_src_out1() {
+        let a: Translation = Translation {
+            src: 10,
+            dst: 50,
+            rng: 10,
+        };
+        let b = Translation {
+            src: 2,
+            dst: 70,
+            rng: 4,
+        };
+        let c = trans_sub_src(&a, &b);
+        assert_eq!(c, vec![a]);
+    }
+
+    #[test]
+    fn test_trans_sub_src_out2() {
+        let a: Translation = Translation {
+            src: 10,
+            dst: 50,
+            rng: 10,
+        };
+        let b = Translation {
+            src: 23,
+            dst: 70,
+            rng: 4,
+        };
+        let c = trans_sub_src(&a, &b);
+        assert_eq!(c, vec![a]);
+    }
+
+    #[test]
+    fn test_trans_sub_src_right_snip() {
+        let a: Translation = Translation {
+            src: 10,
+            dst: 50,
+            rng: 10,
+        };
+        let b = Translation {
+            src: 15,
+            dst: 70,
+            rng: 5,
+        };
+        let c = trans_sub_src(&a, &b);
+        assert_eq!(
+            c,
+            vec![Translation {
+                src: 10,
+                dst: 50,
+                rng: 5
+            }]
+        );
+    }
+
+    #[test]
+    fn test_trans_sub_src_left_snip() {
+        let a: Translation = Translation {
+            src: 10,
+            dst: 50,
+            rng: 10,
+        };
+        let b = Translation {
+            src: 5,
+            dst: 70,
+            rng: 10,
+        };
+        let c = trans_sub_src(&a, &b);
+        assert_eq!(
+            c,
+            vec![Translation {
+                src: 15,
+                dst: 55,
+                rng: 5
+            }]
+        );
+    }
+
+    #[test]
+    fn test_trans_sub_src_poke_hole() {
+        let a: Translation = Translation {
+            src: 10,
+            dst: 50,
+            rng: 10,
+        };
+        let b = Translation {
+            src: 12,
+            dst: 70,
+            rng: 6,
+        };
+        let c = trans_sub_src(&a, &b);
+        assert_eq!(
+            c,
+            vec![
+                Translation {
+                    src: 10,
+                    dst: 50,
+                    rng: 2
+                },
+                Translation {
+                    src: 18,
+                    dst: 58,
+                    rng: 2
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trans_sub_dst_example() {
+        let a: Translation = Translation {
+            src: 50,
+            dst: 52,
+            rng: 48,
+        };
+        let b = Translation {
+            src: 15,
+            dst: 0,
+            rng: 37,
+        };
+        let c = trans_sub_dst(&a, &b);
+        assert_eq!(
+            c,
+            vec![Translation {
+                src: 50,
+                dst: 52,
+                rng: 48
+            }]
+        );
+    }
+
+    #[test]
+    fn test_trans_sub_dst_poke_hole() {
+        let a: Translation = Translation {
+            src: 10,
+            dst: 50,
+            rng: 10,
+        };
+        let b = Translation {
+            src: 52,
+            dst: 70,
+            rng: 6,
+        };
+        let c = trans_sub_dst(&a, &b);
+        assert_eq!(
+            c,
+            vec![
+                Translation {
+                    src: 10,
+                    dst: 50,
+                    rng: 2
+                },
+                Translation {
+                    src: 18,
+                    dst: 58,
+                    rng: 2
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trans_shift_overlap_cover() {
+        let a: Translation = Translation {
+            src: 10,
+            dst: 50,
+            rng: 10,
+        };
+        let b = Translation {
+            src: 40,
+            dst: 70,
+            rng: 30,
+        };
+        let c = trans_shift_overlaps(&a, &b);
+        assert_eq!(
+            c,
+            vec![Translation {
+                src: 10,
+                dst: 80,
+                rng: 10
+            }]
+        );
+    }
+
+    #[test]
+    fn test_trans_shift_overlap_outside() {
+        let a: Translation = Translation {
+            src: 10,
+            dst: 50,
+            rng: 10,
+        };
+        let b = Translation {
+            src: 40,
+            dst: 70,
+            rng: 5,
+        };
+        let c = trans_shift_overlaps(&a, &b);
+        assert_eq!(c, vec![]);
+    }
+
+    #[test]
+    fn test_trans_shift_overlap_left() {
+        let a: Translation = Translation {
+            src: 10,
+            dst: 50,
+            rng: 10,
+        };
+        let b = Translation {
+            src: 55,
+            dst: 70,
+            rng: 10,
+        };
+        let c = trans_shift_overlaps(&a, &b);
+        assert_eq!(
+            c,
+            vec![Translation {
+                src: 15,
+                dst: 70,
+                rng: 5
+            }]
+        );
+    }
+
+    #[test]
+    fn test_trans_shift_overlap_right() {
+        let a: Translation = Translation {
+            src: 10,
+            dst: 50,
+            rng: 10,
+        };
+        let b = Translation {
+            src: 45,
+            dst: 70,
+            rng: 10,
+        };
+        let c = trans_shift_overlaps(&a, &b);
+        assert_eq!(
+            c,
+            vec![Translation {
+                src: 10,
+                dst: 75,
+                rng: 5
+            }]
+        );
+    }
+
+    #[test]
+    fn test_trans_shift_overlap_poke() {
+        let a: Translation = Translation {
+            src: 10,
+            dst: 50,
+            rng: 30,
+        };
+        let b = Translation {
+            src: 60,
+            dst: 100,
+            rng: 10,
+        };
+        let c = trans_shift_overlaps(&a, &b);
+        assert_eq!(
+            c,
+            vec![Translation {
+                src: 20,
+                dst: 100,
+                rng: 10
+            }]
+        );
+    }
+
+    fn small_translations() -> impl Strategy<Value = Vec<Translation>> {
+        prop::collection::vec((0u64..60, 0u64..60, 0u64..20), 0..5).prop_map(|ts| {
+            ts.into_iter()
+                .map(|(src, dst, rng)| Translation { src, dst, rng })
+                .collect()
+        })
+    }
+
+    fn small_map() -> impl Strategy<Value = Map> {
+        small_translations().prop_map(Map::new)
+    }
+
+    fn small_translation() -> impl Strategy<Value = Translation> {
+        (0u64..60, 0u64..60, 0u64..20).prop_map(|(src, dst, rng)| Translation { src, dst, rng })
+    }
+
+    fn small_ranges() -> impl Strategy<Value = Vec<RangeInclusive<u64>>> {
+        prop::collection::vec((0u64..80, 0u64..20).prop_map(|(s, l)| span(s, l)), 0..5)
+    }
+
+    /// Every value in the ranges, sorted and without doubles
+    fn points(ranges: &[RangeInclusive<u64>]) -> Vec<u64> {
+        let mut points = ranges.iter().cloned().flatten().collect::<Vec<_>>();
+        points.sort();
+        points.dedup();
+        points
+    }
+
+    /// Write an almanac the way the puzzle input does
+    fn almanac_text(seeds: &[u64], maps: &[Vec<Translation>]) -> String {
+        let seeds = seeds.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let mut text = format!("seeds: {}", seeds.join(" "));
+        for (i, map) in maps.iter().enumerate() {
+            text += &format!("\n\nc{}-to-c{} map:", i, i + 1);
+            for t in map {
+                text += &format!("\n{} {} {}", t.dst, t.src, t.rng);
+            }
+        }
+        text
+    }
+
+    proptest! {
+        #[test]
+        fn prop_translate_first_wins(ts in small_translations()) {
+            let map = Map::new(ts.clone());
+            assert_contiguous(&map);
+            for v in 0..100 {
+                let expected = ts.iter().find(|t| t.in_range(v)).map_or(v, |t| t.translate(v));
+                prop_assert_eq!(map.translate(v), expected);
+            }
+        }
+
+        #[test]
+        fn prop_overlaps_cover_every_overlapping_range(ts in small_translations()) {
+            let map = Map::new(ts.clone());
+            let ranges = ts.iter().map(|t| t.range()).filter(|r| !r.is_empty()).collect::<Vec<_>>();
+            let overlapping = |a: &RangeInclusive<u64>, b: &RangeInclusive<u64>| {
+                a.start() <= b.end() && b.start() <= a.end()
+            };
+            let pairs = map.overlaps();
+            for (first, second) in &pairs {
+                prop_assert!(overlapping(first, second));
+            }
+            // every range that overlaps another one shows up in a pair
+            for (i, a) in ranges.iter().enumerate() {
+                let overlaps_any = ranges.iter().enumerate().any(|(j, b)| i != j && overlapping(a, b));
+                let reported = pairs.iter().any(|(first, second)| first == a || second == a);
+                prop_assert_eq!(overlaps_any, reported, "{:?}", a);
+            }
+        }
+
+        #[test]
+        fn prop_translate_range_matches_translate(map in small_map(), start in 0u64..80, len in 0u64..30) {
+            let translated = map.translate_range(&span(start, len));
+            let mut expected = span(start, len).map(|v| map.translate(v)).collect::<Vec<_>>();
+            expected.sort();
+            expected.dedup();
+            let mut values = translated.into_iter().flatten().collect::<Vec<_>>();
+            values.sort();
+            prop_assert_eq!(values, expected);
+        }
+
+        #[test]
+        fn prop_translate_set_matches_translate(map in small_map(), ranges in small_ranges()) {
+            let set = ranges.iter().cloned().collect::<RangeSet<_>>();
+            let translated = map.translate_set(&set);
+            prop_assert_eq!(
+                RangeSet::normalise(translated.ranges().to_vec()),
+                translated.ranges().to_vec()
+            );
+            let mut expected = points(&ranges).into_iter().map(|v| map.translate(v)).collect::<Vec<_>>();
+            expected.sort();
+            expected.dedup();
+            prop_assert_eq!(points(translated.ranges()), expected);
+        }
+
+        #[test]
+        fn prop_simplify_ranges(ranges in small_ranges()) {
+            let simplified = Map::simplify_ranges(ranges.clone());
+            prop_assert!(simplified.iter().all(|r| r.start() <= r.end()));
+            // sorted, and with a gap between every two ranges
+            prop_assert!(simplified.windows(2).all(|w| w[0].end() + 1 < *w[1].start()));
+            prop_assert_eq!(points(&simplified), points(&ranges));
+        }
+
+        #[test]
+        fn prop_trans_helpers(a in small_translation(), b in small_translation()) {
+            let covers = |ts: &[Translation], v: u64| ts.iter().find(|t| t.in_range(v)).copied();
+            let shifted = trans_shift_overlaps(&a, &b);
+            let sub_dst = trans_sub_dst(&a, &b);
+            let sub_src = trans_sub_src(&a, &b);
+            for v in 0..100 {
+                let through_b = a.in_range(v) && b.in_range(a.translate(v));
+                prop_assert_eq!(
+                    covers(&shifted, v).map(|t| t.translate(v)),
+                    through_b.then(|| b.translate(a.translate(v)))
+                );
+                prop_assert_eq!(
+                    covers(&sub_dst, v).map(|t| t.translate(v)),
+                    (a.in_range(v) && !through_b).then(|| a.translate(v))
+                );
+                prop_assert_eq!(
+                    covers(&sub_src, v).map(|t| t.translate(v)),
+                    (a.in_range(v) && !b.in_range(v)).then(|| a.translate(v))
+                );
+            }
+        }
+
+        #[test]
+        fn prop_display_round_trip(maps in prop::collection::vec(small_map(), 0..4)) {
+            let almanac = Almanac {
+                seeds: vec![1, 2],
+                maps: maps
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, m)| m.named(&format!("c{}", i), &format!("c{}", i + 1)))
+                    .collect(),
+            };
+            prop_assert_eq!(parse_input(&almanac.to_string()), Ok(almanac));
+        }
+
+        #[test]
+        fn prop_solve2_matches_solve2b(
+            maps in prop::collection::vec(small_translations(), 1..4),
+            seeds in prop::collection::vec((0u64..80, 1u64..10), 1..4),
+        ) {
+            let seeds = seeds.into_iter().flat_map(|(s, l)| [s, l]).collect::<Vec<_>>();
+            let input = almanac_text(&seeds, &maps);
+            prop_assert_eq!(solve2(&input), solve2b(&input));
+
+            // and part 1 gets the same answer when we give it every seed
+            let every_seed = seed_ranges(&seeds).into_iter().flatten().collect::<Vec<_>>();
+            prop_assert_eq!(solve(&almanac_text(&every_seed, &maps)), solve2(&input));
+        }
+
+        #[test]
+        fn prop_lowest_location_ranges(
+            categories in prop::collection::vec(small_map(), 1..4),
+            seeds in prop::collection::vec((0u64..80, 1u64..10), 1..4),
+        ) {
+            let seeds = seeds.into_iter().flat_map(|(s, l)| [s, l]).collect::<Vec<_>>();
+            // following every single seed is what part 1 does
+            let every_seed = seed_ranges(&seeds).into_iter().flatten().collect::<Vec<_>>();
+            prop_assert_eq!(
+                lowest_location_ranges(&seeds, &categories),
+                lowest_location(&every_seed, &categories)
+            );
+        }
+    }
+
+    #[test]
+    fn test_compose() {
+        let first = "100 5 5\n200 10 5".parse::<Map>().unwrap();
+        let second = "0 102 2".parse::<Map>().unwrap();
+        let map = first.compose(&second);
+        assert_eq!(
+            map.translations().copied().collect::<Vec<_>>(),
+            vec![
+                Translation {
+                    src: 5,
+                    dst: 100,
+                    rng: 2
+                },
+                Translation {
+                    src: 7,
+                    dst: 0,
+                    rng: 2
+                },
+                Translation {
+                    src: 9,
+                    dst: 104,
+                    rng: 1
+                },
+                Translation {
+                    src: 10,
+                    dst: 200,
+                    rng: 5
+                },
+                Translation {
+                    src: 102,
+                    dst: 0,
+                    rng: 2
+                },
+            ]
+        );
+
+        // 5..=9 goes there and back again, so only 100..=104 is left
+        let back = "5 100 5".parse::<Map>().unwrap();
+        let map = "100 5 5".parse::<Map>().unwrap().compose(&back);
+        assert_eq!(
+            map.translations().copied().collect::<Vec<_>>(),
+            vec![Translation {
+                src: 100,
+                dst: 5,
+                rng: 5
+            }]
+        );
+    }
+
+    #[test]
+    fn test_inverse() {
+        let map = "50 98 2\n52 50 48".parse::<Map>().unwrap();
+        let inverse = map.inverse().unwrap();
+        for v in 0..120 {
+            assert_eq!(inverse.translate(map.translate(v)), v);
+        }
+
+        // 10 and 20 both end up at 20
+        assert!("20 10 1".parse::<Map>().unwrap().inverse().is_none());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_lowest_location_reverse(
+            categories in prop::collection::vec(small_map(), 0..4),
+            seeds in prop::collection::vec((0u64..80, 1u64..10), 1..4),
+        ) {
+            let seeds = seeds.into_iter().flat_map(|(s, l)| [s, l]).collect::<Vec<_>>();
+            let origin = check_part2(&seeds, &categories).unwrap();
+            prop_assert_eq!(lowest_location(&[origin.seed], &categories), Some(origin.location));
+        }
+
+        #[test]
+        fn prop_compose(a in small_map(), b in small_map()) {
+            let map = a.compose(&b);
+            prop_assert!(map.segments().windows(2).all(|w| w[0].last() == Some(w[1].src - 1)));
+            for v in 0..100 {
+                prop_assert_eq!(map.translate(v), b.translate(a.translate(v)));
+            }
+        }
+
+        #[test]
+        fn prop_inverse(a in small_map()) {
+            if let Some(inverse) = a.inverse() {
+                for v in 0..100 {
+                    prop_assert_eq!(inverse.translate(a.translate(v)), v);
+                }
+            }
+        }
+
+        /// With u8 we can try every value, right up to where things would
+        /// overflow
+        #[test]
+        fn prop_u8_edges(
+            translations in prop::collection::vec(any::<(u8, u8, u8)>(), 0..5),
+            seeds in prop::collection::vec(any::<(u8, u8)>(), 1..4),
+        ) {
+            let translations = translations
+                .into_iter()
+                .map(|(src, dst, rng)| Translation { src, dst, rng })
+                .collect::<Vec<_>>();
+            let map = Map::new(translations.clone());
+            for v in 0..=u8::MAX {
+                let expected = translations.iter().find(|t| t.in_range(v)).map_or(v, |t| t.translate(v));
+                prop_assert_eq!(map.translate(v), expected);
+            }
+
+            // the seed ranges can all be empty, then there's nothing to find
+            let seeds = seeds.into_iter().flat_map(|(s, l)| [s, l]).collect::<Vec<_>>();
+            let maps = [map];
+            let every_seed = seed_ranges(&seeds).into_iter().flatten().collect::<Vec<_>>();
+            match lowest_location(&every_seed, &maps) {
+                Some(lowest) => {
+                    prop_assert_eq!(check_part2(&seeds, &maps).map(|o| o.location), Ok(lowest))
+                }
+                None => prop_assert!(check_part2(&seeds, &maps).is_err()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_u128() {
+        let big = 1u128 << 100;
+        let input = format!(
+            "seeds: {} 10 {} 100
+
+seed-to-soil map:
+0 {} 3
+5 {} 100",
+            big,
+            u128::MAX - 5,
+            big + 5,
+            u128::MAX - 10
+        );
+        let almanac = input.parse::<Almanac<u128>>().unwrap();
+
+        // the second translation only fits for 11 values, and the second seed
+        // range stops at the largest value
+        assert_eq!(almanac.maps[0].translate(u128::MAX), 15);
+        assert_eq!(
+            seed_ranges(&almanac.seeds),
+            vec![big..=big + 9, u128::MAX - 5..=u128::MAX]
+        );
+        assert_eq!(lowest_location(&almanac.seeds, &almanac.maps), Some(10));
+        assert_eq!(
+            check_part2(&almanac.seeds, &almanac.maps),
+            Ok(Origin {
+                location: 0,
+                seed: big + 5
+            })
+        );
+        assert_eq!(
+            almanac.validate(),
+            vec![Diagnostic::Overflow {
+                map: 0,
+                translation: Translation {
+                    src: u128::MAX - 10,
+                    dst: 5,
+                    rng: 100
+                }
+            }]
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use crate::{parse, Answer, ParseError, Solution, SolveError};
+
+#[derive(Debug, PartialEq)]
+pub struct Race {
+    time: u64,
+    distance: u64,
+}
+
+impl Race {
+    pub fn all_strats(&self) -> Vec<(u64, u64)> {
+        (1..(self.time - 1))
+            .map(|hold| (hold, (self.time - hold) * hold))
+            .collect::<Vec<_>>()
+    }
+
+    pub fn winning_strats(&self) -> Vec<u64> {
+        self.all_strats()
+            .into_iter()
+            .filter(|(_, d)| *d > self.distance)
+            .map(|(h, _)| h)
+            .collect()
+    }
+
+    /// Count the winning strategies without collecting them, since the race
+    /// in part 2 has tens of millions of them. The distances are worked out
+    /// in a u128, so long races can't overflow.
+    pub fn nr_winning_strats(&self) -> u64 {
+        (1..self.time.saturating_sub(1))
+            .filter(|&hold| (self.time - hold) as u128 * hold as u128 > self.distance as u128)
+            .count() as u64
+    }
+}
+
+/// Parse input into a vec of Races
+pub fn parse(input: &str) -> Result<Vec<Race>, ParseError> {
+    let mut lines = input.lines();
+    // Take the next line, strip the label, parse the numbers
+    let mut numbers = |label: &str| {
+        let line = lines.next().unwrap_or_default();
+        let numbers = line
+            .strip_prefix(label)
+            .ok_or_else(|| ParseError::new(line, line, format!("'{}'", label)))?
+            .split_whitespace()
+            .map(|n| parse::number(line, n))
+            .collect::<Result<Vec<u64>, _>>()?;
+        Ok((line, numbers))
+    };
+    let (_, times) = numbers("Time:")?;
+    let (line, distances) = numbers("Distance:").map_err(|e: ParseError| e.offset(1))?;
+
+    // every race needs both a time and a distance
+    if distances.len() != times.len() {
+        return Err(ParseError::end_of(line, format!("{} distances", times.len())).offset(1));
+    }
+
+    // should be two lines only
+    if let Some(extra) = lines.find(|l| !l.trim().is_empty()) {
+        return Err(ParseError::new(extra, extra, "end of input").offset(2));
+    }
+
+    // Zip the two lists together, map to Race, collect into a vec
+    Ok(times
+        .into_iter()
+        .zip(distances)
+        .map(|(time, distance)| Race { time, distance })
+        .collect())
+}
+
+/// In part 2 it turns out there's only one race, the spaces between the
+/// numbers are just bad kerning. We glue the digits of all races together,
+/// which gives None if the glued numbers don't fit in a u64.
+pub fn kern(races: &[Race]) -> Option<Race> {
+    Some(Race {
+        time: glue(races.iter().map(|r| r.time))?,
+        distance: glue(races.iter().map(|r| r.distance))?,
+    })
+}
+
+/// Write numbers after each other, shifting the digits so far to the left to
+/// make room for the next number
+fn glue(mut numbers: impl Iterator<Item = u64>) -> Option<u64> {
+    numbers.try_fold(0u64, |glued, n| {
+        let digits = n.checked_ilog10().unwrap_or(0) + 1;
+        glued
+            .checked_mul(10u64.checked_pow(digits)?)?
+            .checked_add(n)
+    })
+}
+
+/// Multiply the number of ways to win each race
+pub fn margin_of_error(races: &[Race]) -> u64 {
+    races.iter().map(|r| r.nr_winning_strats()).product()
+}
+
+pub fn solve(input: &str) -> u64 {
+    margin_of_error(&parse(input).unwrap())
+}
+
+pub fn solve2(input: &str) -> u64 {
+    kern(&parse(input).unwrap()).unwrap().nr_winning_strats()
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Vec<Race>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(margin_of_error(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError> {
+        let race = kern(input)
+            .ok_or_else(|| SolveError::new("the glued together race doesn't fit in a u64"))?;
+        Ok(race.nr_winning_strats().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_race_winning_strats() {
+        let r = Race {
+            time: 7,
+            distance: 9,
+        };
+        assert_eq!(r.winning_strats(), vec![2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse(
+                "Time:      7  15   30
+Distance:   9   40 200"
+            ),
+            Ok(vec![
+                Race {
+                    time: 7,
+                    distance: 9
+                },
+                Race {
+                    time: 15,
+                    distance: 40
+                },
+                Race {
+                    time: 30,
+                    distance: 200
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse("Time: 7 15\nDistance: 9 4o").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 13, "4o"));
+
+        let err = parse("Time: 7 15\nDistance: 9").unwrap_err();
+        assert_eq!((err.line, err.expected.as_str()), (2, "2 distances"));
+
+        let err = parse("Time: 7 15").unwrap_err();
+        assert_eq!((err.line, err.expected.as_str()), (2, "'Distance:'"));
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(
+            solve(
+                "Time:      7  15   30
+Distance:  9  40  200"
+            ),
+            288
+        );
+    }
+
+    #[test]
+    fn test_kern() {
+        assert_eq!(
+            solve2(
+                "Time:      7  15   30
+Distance:  9  40  200"
+            ),
+            71503
+        );
+    }
+
+    #[test]
+    fn test_kern_overflow() {
+        let race = |time, distance| Race { time, distance };
+        assert_eq!(
+            kern(&[race(7, 9), race(0, 40), race(30, 200)]),
+            Some(race(7030, 940200))
+        );
+
+        // 20 digits is one too many for a u64
+        let races = (1..=10).map(|i| race(i * 10, i)).collect::<Vec<_>>();
+        assert_eq!(kern(&races), None);
+        assert!(Day06::part1(&races).is_ok());
+        assert!(Day06::part2(&races).is_err());
+    }
+
+    #[test]
+    fn test_solve2() {
+        assert_eq!(
+            solve(
+                "Time:      71530
+Distance:  940200"
+            ),
+            71503
+        );
+    }
+}
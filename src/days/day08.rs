@@ -0,0 +1,998 @@
+use itertools::Itertools;
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use crate::{
+    math::{crt_pair, gcd, Congruence},
+    Answer, ParseError, Solution, SolveError,
+};
+
+/// We give up on answers past this many steps, unless the map says otherwise
+pub const MAX_STEPS: usize = 1 << 48;
+
+/// A node is a name
+pub type Node = String;
+
+/// Nodes are known by their index in the map, which is a lot cheaper to pass
+/// around than their names
+pub type NodeId = u32;
+
+/// An instruction can be left or right
+#[derive(Debug, PartialEq)]
+pub enum Instruction {
+    Left,
+    Right,
+}
+
+impl Instruction {
+    /// Where to look in a node's `[left, right]` pair
+    fn index(&self) -> usize {
+        match self {
+            Instruction::Left => 0,
+            Instruction::Right => 1,
+        }
+    }
+}
+
+/// A route is a series of instructions to go left or right
+pub type Route = Vec<Instruction>;
+
+pub fn parse_route(input: &str) -> Result<Route, ParseError> {
+    let mut route = Vec::new();
+
+    for (i, c) in input.char_indices() {
+        match c {
+            'L' => route.push(Instruction::Left),
+            'R' => route.push(Instruction::Right),
+            _ => {
+                let token = &input[i..i + c.len_utf8()];
+                return Err(ParseError::new(input, token, "'L' or 'R'"));
+            }
+        }
+    }
+
+    if route.is_empty() {
+        return Err(ParseError::end_of(input, "a route"));
+    }
+
+    Ok(route)
+}
+
+/// There's the definition of a Map, which is a series of paths
+/// And a route to take
+///
+/// Every node name gets a number, and the paths are a table with the left and
+/// right node for each number.
+#[derive(Debug)]
+pub struct Map {
+    route: Route,
+    names: Vec<Node>,
+    ids: HashMap<Node, NodeId>,
+    paths: Vec<[NodeId; 2]>,
+    max_steps: usize,
+}
+
+impl Map {
+    /// Give up on answers past this many steps
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Get the number of a node, giving it a new one if we haven't seen it
+    /// before. The node goes nowhere until its own path shows up.
+    fn intern(&mut self, name: &str) -> NodeId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.names.len() as NodeId;
+        self.names.push(name.to_owned());
+        self.ids.insert(name.to_owned(), id);
+        self.paths.push([id, id]);
+        id
+    }
+
+    /// Look up a node by name
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    /// The name of a node
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id as usize]
+    }
+
+    /// How many different nodes the map has
+    pub fn node_count(&self) -> usize {
+        self.names.len()
+    }
+
+    /// The names of the left and right nodes of a node
+    pub fn path(&self, name: &str) -> Option<(&str, &str)> {
+        let [left, right] = self.paths[self.id(name)? as usize];
+        Some((self.name(left), self.name(right)))
+    }
+
+    /// Follow one instruction from a node
+    pub fn next(&self, id: NodeId, instruction: &Instruction) -> NodeId {
+        self.paths[id as usize][instruction.index()]
+    }
+}
+
+/// A map can be parsed from a string
+impl FromStr for Map {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut lines = input.lines();
+        let first_line = lines.next().unwrap_or_default();
+        let route = parse_route(first_line)?;
+
+        let mut map = Map {
+            route,
+            names: vec![],
+            ids: HashMap::new(),
+            paths: vec![],
+            max_steps: MAX_STEPS,
+        };
+
+        // which nodes have a path of their own, and where each node was
+        // first gone to
+        let mut defined = vec![];
+        let mut mentioned = vec![];
+
+        // iterate over the rest of the lines
+        for (i, line) in lines.enumerate() {
+            // skip empty lines
+            if line.is_empty() {
+                continue;
+            }
+
+            let (from, left, right) = parse_path(line).map_err(|e| e.offset(i + 1))?;
+
+            // add the path to the table
+            let from = map.intern(from);
+            let paths = [map.intern(left), map.intern(right)];
+            map.paths[from as usize] = paths;
+
+            defined.resize(map.node_count(), false);
+            mentioned.resize(map.node_count(), None);
+            defined[from as usize] = true;
+            for (id, name) in paths.into_iter().zip([left, right]) {
+                mentioned[id as usize].get_or_insert((i + 1, line, name));
+            }
+        }
+
+        // a ghost on a node without a path would have nowhere to go
+        let undefined = mentioned
+            .iter()
+            .zip(&defined)
+            .filter(|(_, &defined)| !defined)
+            .filter_map(|(mention, _)| *mention)
+            .min_by_key(|&(offset, ..)| offset);
+        if let Some((offset, line, name)) = undefined {
+            return Err(ParseError::new(line, name, "a node with a path of its own").offset(offset));
+        }
+
+        Ok(map)
+    }
+}
+
+/// Parse a path like `AAA = (BBB, CCC)` into its three nodes
+fn parse_path(line: &str) -> Result<(&str, &str, &str), ParseError> {
+    // split the line into two parts
+    let (from, to) = line
+        .split_once(" = ")
+        .ok_or_else(|| ParseError::end_of(line, "' = '"))?;
+
+    // split the to part into two nodes
+    let nodes = to
+        .strip_prefix('(')
+        .and_then(|to| to.strip_suffix(')'))
+        .and_then(|to| to.split_once(", "))
+        .ok_or_else(|| ParseError::new(line, to, "'(<left>, <right>)'"))?;
+
+    Ok((from, nodes.0, nodes.1))
+}
+
+/// Why we couldn't get from one place to another
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum NavigationError {
+    /// There's no node with this name
+    UnknownNode(Node),
+    /// None of the nodes are a place to start from
+    NoStart,
+    /// None of the nodes are a place to end up
+    NoEnd,
+    /// Walking from `from` never gets to any of the nodes in `to`
+    Unreachable { from: Node, to: Vec<Node> },
+    /// Everyone gets to an end node, but never all at the same time
+    NeverTogether,
+    /// There's no answer within this many steps
+    TooManySteps(usize),
+}
+
+impl fmt::Display for NavigationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NavigationError::UnknownNode(name) => write!(f, "there's no node '{}'", name),
+            NavigationError::NoStart => write!(f, "there's no node to start from"),
+            NavigationError::NoEnd => write!(f, "there's no node to go to"),
+            NavigationError::Unreachable { from, to } => match to.as_slice() {
+                [to] => write!(f, "'{}' can't be reached from '{}'", to, from),
+                to => write!(
+                    f,
+                    "none of '{}' can be reached from '{}'",
+                    to.join("', '"),
+                    from
+                ),
+            },
+            NavigationError::NeverTogether => {
+                write!(f, "the ghosts are never on an end node together")
+            }
+            NavigationError::TooManySteps(max) => {
+                write!(f, "they're not all on an end node within {} steps", max)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NavigationError {}
+
+impl Map {
+    /// Get the steps from AAA to ZZZ
+    pub fn get_steps(&self) -> Result<usize, NavigationError> {
+        self.steps_from("AAA", "ZZZ")
+    }
+
+    /// Follow every ghost from a node ending with 'A' until they're all on a
+    /// node ending with 'Z'
+    pub fn get_better_steps(&self) -> Result<usize, NavigationError> {
+        self.steps_between(|name| name.ends_with('A'), |name| name.ends_with('Z'))
+    }
+
+    /// The steps from one node to another, by name
+    pub fn steps_from(&self, from: &str, to: &str) -> Result<usize, NavigationError> {
+        for name in [from, to] {
+            if self.id(name).is_none() {
+                return Err(NavigationError::UnknownNode(name.to_owned()));
+            }
+        }
+        self.steps_between(|name| name == from, |name| name == to)
+    }
+
+    /// The steps from the nodes `from` matches to the nodes `to` matches,
+    /// see `matches_pattern`. A pattern without a `*` names a single node,
+    /// which has to be on the map.
+    pub fn steps_matching(&self, from: &str, to: &str) -> Result<usize, NavigationError> {
+        for pattern in [from, to] {
+            if !pattern.contains('*') && self.id(pattern).is_none() {
+                return Err(NavigationError::UnknownNode(pattern.to_owned()));
+            }
+        }
+        self.steps_between(
+            |name| matches_pattern(from, name),
+            |name| matches_pattern(to, name),
+        )
+    }
+
+    /// Start a ghost on every node that `start` matches, and find the first
+    /// step at which they're all on a node that `end` matches.
+    pub fn steps_between(
+        &self,
+        start: impl Fn(&str) -> bool,
+        end: impl Fn(&str) -> bool,
+    ) -> Result<usize, NavigationError> {
+        let ids = 0..self.node_count() as NodeId;
+        let starts = ids.clone().filter(|&id| start(self.name(id))).collect_vec();
+        let is_end = ids.map(|id| end(self.name(id))).collect_vec();
+        if starts.is_empty() {
+            return Err(NavigationError::NoStart);
+        }
+        if !is_end.contains(&true) {
+            return Err(NavigationError::NoEnd);
+        }
+
+        // make sure every ghost gets to an end node at all before we go
+        // looking for their cycles
+        let reaches = self.reaches_end(&is_end);
+        if let Some(&from) = starts
+            .iter()
+            .find(|&&id| !reaches[self.state(self.next(id, &self.route[0]), 1)])
+        {
+            return Err(NavigationError::Unreachable {
+                from: self.name(from).to_owned(),
+                to: (0..self.node_count() as NodeId)
+                    .filter(|&id| is_end[id as usize])
+                    .map(|id| self.name(id).to_owned())
+                    .collect(),
+            });
+        }
+
+        let cycles = starts
+            .into_iter()
+            .map(|id| self.find_cycle(id, |id| is_end[id as usize]))
+            .collect_vec();
+
+        first_common_hit(&cycles, self.max_steps)
+    }
+
+    /// The number of a state: a node together with where we are in the route
+    fn state(&self, node: NodeId, step: usize) -> usize {
+        let n = self.route.len();
+        node as usize * n + step % n
+    }
+
+    /// For every state, can we get to an end node from there (or are we on
+    /// one already)? Every state leads to exactly one other, so we turn that
+    /// around and spread out from the end nodes, which covers all states in
+    /// one go.
+    fn reaches_end(&self, is_end: &[bool]) -> Vec<bool> {
+        let n = self.route.len();
+        let count = self.node_count() * n;
+        let next_state = |state: usize| {
+            let (node, step) = (state / n, state % n);
+            self.state(self.next(node as NodeId, &self.route[step]), step + 1)
+        };
+
+        // the states leading into each state, all in one list with `starts`
+        // pointing at where each state's part begins
+        let mut starts = vec![0; count + 1];
+        for state in 0..count {
+            starts[next_state(state) + 1] += 1;
+        }
+        for i in 0..count {
+            starts[i + 1] += starts[i];
+        }
+        let mut fill = starts.clone();
+        let mut before = vec![0; count];
+        for state in 0..count {
+            let next = next_state(state);
+            before[fill[next]] = state;
+            fill[next] += 1;
+        }
+
+        let mut reaches = (0..count).map(|state| is_end[state / n]).collect_vec();
+        let mut todo = (0..count).filter(|&state| reaches[state]).collect_vec();
+        while let Some(state) = todo.pop() {
+            for &prev in &before[starts[state]..starts[state + 1]] {
+                if !reaches[prev] {
+                    reaches[prev] = true;
+                    todo.push(prev);
+                }
+            }
+        }
+        reaches
+    }
+
+    /// Walk from a node until we're back in a state we've seen before. The
+    /// state is the node together with where we are in the route, so after
+    /// at most (nodes × route length) steps everything starts repeating.
+    ///
+    /// We stop walking once we're past the map's `max_steps`, since nothing
+    /// after that can be an answer. The cycle then only has a tail, with the
+    /// hits we saw along the way, and a length of 0.
+    pub fn find_cycle(&self, start: NodeId, is_end: impl Fn(NodeId) -> bool) -> Cycle {
+        let n = self.route.len();
+        let mut seen = vec![usize::MAX; self.node_count() * n];
+        let mut hits = vec![];
+        let mut node = start;
+        let mut step = 0;
+
+        loop {
+            let state = self.state(node, step);
+            if seen[state] != usize::MAX {
+                let tail = seen[state];
+                // we're back at the state from step `tail`, so a hit there is
+                // really the first hit of the cycle
+                hits.retain(|&h| h != tail);
+                if is_end(node) {
+                    hits.push(step);
+                }
+                return Cycle {
+                    tail,
+                    length: step - tail,
+                    hits,
+                };
+            }
+            if step > self.max_steps {
+                return Cycle {
+                    tail: step,
+                    length: 0,
+                    hits,
+                };
+            }
+            seen[state] = step;
+            if step > 0 && is_end(node) {
+                hits.push(step);
+            }
+            node = self.next(node, &self.route[step % n]);
+            step += 1;
+        }
+    }
+}
+
+/// How a ghost walks around the map. For the first `tail` steps it's on its
+/// way to a cycle, after that it goes round the same `length` steps forever.
+/// A length of 0 means we stopped looking after the tail, and don't know
+/// what happens after that.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Cycle {
+    pub tail: usize,
+    pub length: usize,
+    /// The steps at which we're on an end node, counting from 1. The ones
+    /// before `tail` only happen once, the ones after it come back every
+    /// `length` steps.
+    pub hits: Vec<usize>,
+}
+
+impl Cycle {
+    /// Are we on an end node after this many steps?
+    pub fn hit_at(&self, steps: usize) -> bool {
+        if steps < self.tail {
+            self.hits.contains(&steps)
+        } else {
+            self.length > 0
+                && self
+                    .cyclic_hits()
+                    .any(|h| (steps - self.tail) % self.length == (h - self.tail) % self.length)
+        }
+    }
+
+    fn cyclic_hits(&self) -> impl Iterator<Item = usize> + '_ {
+        self.hits.iter().copied().filter(|&h| h > self.tail)
+    }
+}
+
+/// The first step (after the start) at which every cycle is on an end node,
+/// as long as that's at most `max_steps`
+pub fn first_common_hit(cycles: &[Cycle], max_steps: usize) -> Result<usize, NavigationError> {
+    let longest = cycles
+        .iter()
+        .max_by_key(|c| c.tail)
+        .ok_or(NavigationError::NoStart)?;
+
+    // before the longest tail is over, only its hits can be the answer
+    let mut early = longest
+        .hits
+        .iter()
+        .filter(|&&h| h < longest.tail && h <= max_steps);
+    if let Some(&h) = early.find(|&&h| cycles.iter().all(|c| c.hit_at(h))) {
+        return Ok(h);
+    }
+
+    // after that everyone is in their cycle, so we need a step that lines up
+    // with one of the hits of every cycle. Every cycle can have lots of hits,
+    // so rather than trying every combination we only keep the steps below a
+    // bound, and double the bound until we find one or get to `max_steps`.
+    // a cycle we stopped looking for doesn't tell us anything past its tail
+    let lowest = longest.tail.max(1);
+    if lowest > max_steps || cycles.iter().any(|c| c.length == 0) {
+        return Err(NavigationError::TooManySteps(max_steps));
+    }
+    let mut cycles = cycles.iter().collect_vec();
+    cycles.sort_by_key(|c| c.hits.len());
+    let mut bound = lowest;
+    loop {
+        bound = bound.saturating_mul(2).min(max_steps);
+        match search_below(&cycles, lowest as u128, bound as u128) {
+            Search::Found(steps) => return Ok(steps as usize),
+            Search::Never => return Err(NavigationError::NeverTogether),
+            Search::PastBound if bound == max_steps => {
+                return Err(NavigationError::TooManySteps(max_steps))
+            }
+            Search::PastBound => {}
+        }
+    }
+}
+
+/// What looking for a common hit below a bound turned up
+enum Search {
+    Found(u128),
+    /// There's no common hit at all
+    Never,
+    /// There might be one past the bound
+    PastBound,
+}
+
+/// Look for the first step from `lowest` up to `bound` at which every cycle
+/// is on an end node, with all cycles past their tail by `lowest`.
+///
+/// We fold in the cycles one at a time, keeping the steps that work for all
+/// cycles so far as remainders modulo the lcm of their lengths. A remainder
+/// whose first step is past the bound is dropped right away, so there are
+/// never more of them than steps below the bound. Once the lcm is past the
+/// bound every remainder is down to a single step, and the cycles that are
+/// left only have to be checked against it.
+fn search_below(cycles: &[&Cycle], lowest: u128, bound: u128) -> Search {
+    let mut modulus = 1;
+    let mut candidates = vec![Congruence::new(0, 1)];
+    let mut past_bound = false;
+
+    for c in cycles {
+        let length = c.length as u128;
+        let g = gcd(modulus, length);
+        // a remainder can only line up with the hits that agree with it on
+        // what the two moduli have in common
+        let mut classes: HashMap<u128, Vec<Congruence>> = HashMap::new();
+        for h in c.cyclic_hits() {
+            let hit = Congruence::new(h as u128, length);
+            classes.entry(hit.remainder % g).or_default().push(hit);
+        }
+
+        let mut next = vec![];
+        for r in &candidates {
+            let Some(hits) = classes.get(&(r.remainder % g)) else {
+                continue;
+            };
+            if modulus > bound {
+                let steps = r.first_from(lowest).unwrap_or(u128::MAX);
+                if hits.iter().any(|h| steps % length == h.remainder) {
+                    next.push(*r);
+                } else {
+                    past_bound = true;
+                }
+                continue;
+            }
+            // the moduli are at most the bound and a cycle length, so this
+            // can't overflow, and the remainders agree so it can't fail
+            for combined in hits.iter().filter_map(|&h| crt_pair(*r, h).ok()) {
+                if combined.first_from(lowest).is_some_and(|s| s <= bound) {
+                    next.push(combined);
+                } else {
+                    past_bound = true;
+                }
+            }
+        }
+        if modulus <= bound {
+            modulus = modulus / g * length;
+        }
+        candidates = next;
+        if candidates.is_empty() {
+            break;
+        }
+    }
+
+    match candidates.iter().filter_map(|r| r.first_from(lowest)).min() {
+        Some(steps) => Search::Found(steps),
+        None if past_bound => Search::PastBound,
+        None => Search::Never,
+    }
+}
+
+/// Does a node name match a pattern? A `*` in the pattern matches any number
+/// of characters, so `*Z` is every node ending with a Z. Without a `*` only
+/// that exact node matches.
+pub fn matches_pattern(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        Some((prefix, suffix)) => {
+            name.len() >= prefix.len() + suffix.len()
+                && name.starts_with(prefix)
+                && name.ends_with(suffix)
+        }
+        None => name == pattern,
+    }
+}
+
+pub fn solve(input: &str) -> Result<usize, SolveError> {
+    let map = input.parse::<Map>()?;
+    Ok(map.get_steps()?)
+}
+
+pub fn solve2(input: &str) -> Result<usize, SolveError> {
+    let map = input.parse::<Map>()?;
+    Ok(map.get_better_steps()?)
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Map;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(input.get_steps()?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(input.get_better_steps()?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            solve(
+                "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)"
+            ),
+            Ok(6)
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+        assert_eq!(solve2(input), Ok(6));
+
+        // the example for part 2 has no AAA, which part 1 reports instead of
+        // panicking
+        let map = Day08::parse(input).unwrap();
+        assert_eq!(
+            Day08::part1(&map),
+            Err(SolveError::new("there's no node 'AAA'"))
+        );
+    }
+
+    #[test]
+    fn test_cycles() {
+        // 1 loops in 4 steps after 1 step, and 2 in 3. That gives 12 if we
+        // assume the loops start right away, but they line up at 6. 3 is on
+        // an end node every other step.
+        let map = "L
+
+1A = (1B, 1B)
+1B = (1Z, 1Z)
+1Z = (1C, 1C)
+1C = (1D, 1D)
+1D = (1B, 1B)
+2A = (2B, 2B)
+2B = (2C, 2C)
+2C = (2Z, 2Z)
+2Z = (2B, 2B)
+3A = (3B, 3B)
+3B = (3Z, 3Z)
+3Z = (3C, 3C)
+3C = (33Z, 33Z)
+33Z = (3B, 3B)"
+            .parse::<Map>()
+            .unwrap();
+
+        let is_end = |id| map.name(id).ends_with('Z');
+        let cycle = map.find_cycle(map.id("1A").unwrap(), is_end);
+        assert_eq!(
+            cycle,
+            Cycle {
+                tail: 1,
+                length: 4,
+                hits: vec![2]
+            }
+        );
+        assert!(cycle.hit_at(6) && !cycle.hit_at(4));
+        assert_eq!(
+            map.find_cycle(map.id("3A").unwrap(), is_end).hits,
+            vec![2, 4]
+        );
+        assert_eq!(map.get_better_steps(), Ok(6));
+    }
+
+    #[test]
+    fn test_first_common_hit() {
+        // a hit on the way to the cycle
+        let early = Cycle {
+            tail: 2,
+            length: 1,
+            hits: vec![1],
+        };
+        let odd = Cycle {
+            tail: 0,
+            length: 2,
+            hits: vec![1],
+        };
+        let even = Cycle {
+            tail: 0,
+            length: 2,
+            hits: vec![2],
+        };
+        let never = Err(NavigationError::NeverTogether);
+        assert_eq!(
+            first_common_hit(&[early.clone(), odd.clone()], MAX_STEPS),
+            Ok(1)
+        );
+        assert_eq!(first_common_hit(&[early, even.clone()], MAX_STEPS), never);
+        assert_eq!(first_common_hit(&[odd, even], MAX_STEPS), never);
+        assert_eq!(
+            first_common_hit(&[], MAX_STEPS),
+            Err(NavigationError::NoStart)
+        );
+
+        // moduli that aren't coprime: 4 mod 6 and 6 mod 8 meet at 22
+        let a = Cycle {
+            tail: 0,
+            length: 6,
+            hits: vec![4],
+        };
+        let b = Cycle {
+            tail: 0,
+            length: 8,
+            hits: vec![6],
+        };
+        assert_eq!(first_common_hit(&[a.clone(), b.clone()], MAX_STEPS), Ok(22));
+        assert_eq!(
+            first_common_hit(&[a, b], 21),
+            Err(NavigationError::TooManySteps(21))
+        );
+    }
+
+    #[test]
+    fn test_first_common_hit_many_hits() {
+        // every ghost goes round 7 × p steps, and is on an end node every
+        // 7th of those. Trying every combination of hits would take about
+        // 5 × 10^13 goes, but only the steps that are 3 mod 7 ever line up.
+        let primes = [11, 13, 17, 19, 23, 29, 31, 37];
+        let cycle = |tail: usize, p: usize| Cycle {
+            tail,
+            length: 7 * p,
+            hits: (tail + 1..=tail + 7 * p).filter(|h| h % 7 == 3).collect(),
+        };
+        let cycles = primes.iter().map(|&p| cycle(0, p)).collect_vec();
+        assert_eq!(first_common_hit(&cycles, MAX_STEPS), Ok(3));
+
+        // the same, but one ghost takes 100 steps to get to its cycle
+        let mut cycles = cycles;
+        cycles.push(cycle(100, 41));
+        assert_eq!(first_common_hit(&cycles, MAX_STEPS), Ok(101));
+
+        // and one more that's only ever on an end node at 4 mod 7, which
+        // never lines up with the others
+        cycles.push(Cycle {
+            tail: 0,
+            length: 14,
+            hits: vec![4, 11],
+        });
+        assert_eq!(
+            first_common_hit(&cycles, MAX_STEPS),
+            Err(NavigationError::NeverTogether)
+        );
+    }
+
+    /// A cycle with the hits picked out by the bits of the masks
+    fn cycle_strategy() -> impl Strategy<Value = Cycle> {
+        (0usize..6, 1usize..9, any::<u8>(), any::<u16>()).prop_map(
+            |(tail, length, early, cyclic)| Cycle {
+                tail,
+                length,
+                hits: (1..tail)
+                    .filter(|h| early >> h & 1 == 1)
+                    .chain(
+                        (1..=length)
+                            .filter(|k| cyclic >> k & 1 == 1)
+                            .map(|k| tail + k),
+                    )
+                    .collect(),
+            },
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn prop_first_common_hit(
+            cycles in prop::collection::vec(cycle_strategy(), 1..4),
+            max_steps in 1usize..400,
+        ) {
+            // past the longest tail everything repeats after the lcm of the
+            // lengths, so if they're never together before that they never are
+            let lcm = cycles.iter().fold(1, |lcm, c| lcm / gcd(lcm, c.length as u128) * c.length as u128);
+            let limit = cycles.iter().map(|c| c.tail).max().unwrap() + lcm as usize;
+            let first = (1..=limit).find(|&s| cycles.iter().all(|c| c.hit_at(s)));
+
+            let found = first_common_hit(&cycles, max_steps);
+            match first {
+                Some(steps) if steps <= max_steps => prop_assert_eq!(found, Ok(steps)),
+                Some(_) => prop_assert_eq!(found, Err(NavigationError::TooManySteps(max_steps))),
+                None if max_steps >= limit => prop_assert_eq!(found, Err(NavigationError::NeverTogether)),
+                // we can't always tell whether it's never or just later
+                None => prop_assert!(found.is_err()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_steps_between() {
+        let map = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)"
+            .parse::<Map>()
+            .unwrap();
+
+        let between =
+            |from, to| map.steps_between(|n| matches_pattern(from, n), |n| matches_pattern(to, n));
+        assert_eq!(between("11A", "11Z"), Ok(2));
+        assert_eq!(between("22A", "22Z"), Ok(3));
+        assert_eq!(between("*A", "*Z"), Ok(6));
+        assert_eq!(between("22A", "22C"), Ok(2));
+        assert_eq!(between("X*", "XXX"), Ok(1));
+
+        assert!(matches_pattern("1*Z", "11Z"));
+        assert!(!matches_pattern("11*1Z", "11Z"));
+        assert!(!matches_pattern("11", "11Z"));
+    }
+
+    #[test]
+    fn test_navigation_errors() {
+        let map = "LR
+
+AAA = (BBB, XXX)
+BBB = (XXX, AAA)
+CCC = (ZZZ, ZZZ)
+ZZZ = (ZZZ, CCC)
+11A = (11Z, 11Z)
+11Z = (11A, 11A)
+22A = (22B, 22B)
+22B = (22A, 22A)
+44A = (44B, 44B)
+44B = (44Z, 44Z)
+44Z = (44B, 44B)
+XXX = (XXX, XXX)"
+            .parse::<Map>()
+            .unwrap();
+
+        // AAA only ever goes round past BBB and XXX
+        assert_eq!(
+            map.get_steps(),
+            Err(NavigationError::Unreachable {
+                from: "AAA".into(),
+                to: vec!["ZZZ".into()]
+            })
+        );
+        assert_eq!(
+            map.steps_from("AAA", "NOPE"),
+            Err(NavigationError::UnknownNode("NOPE".into()))
+        );
+        assert_eq!(map.steps_from("CCC", "ZZZ"), Ok(1));
+
+        let between =
+            |from, to| map.steps_between(|n| matches_pattern(from, n), |n| matches_pattern(to, n));
+        assert_eq!(between("NOPE", "ZZZ"), Err(NavigationError::NoStart));
+        assert_eq!(between("AAA", "NOPE"), Err(NavigationError::NoEnd));
+
+        // patterns without a '*' have to name a node
+        let unknown = |name: &str| Err(NavigationError::UnknownNode(name.into()));
+        assert_eq!(map.steps_matching("NOPE", "*Z"), unknown("NOPE"));
+        assert_eq!(map.steps_matching("AAA", "QQQ"), unknown("QQQ"));
+        assert_eq!(
+            map.steps_matching("Q*", "*Z"),
+            Err(NavigationError::NoStart)
+        );
+        assert_eq!(map.steps_matching("*A", "Q*"), Err(NavigationError::NoEnd));
+        assert_eq!(map.steps_matching("44A", "*Z"), Ok(2));
+        assert_eq!(
+            between("22A", "*Z").unwrap_err().to_string(),
+            "none of 'ZZZ', '11Z', '44Z' can be reached from '22A'"
+        );
+
+        // 11A is on 11Z after odd steps and 44A on 44Z after even ones
+        assert_eq!(between("11A", "*Z"), Ok(1));
+        assert_eq!(between("44A", "*Z"), Ok(2));
+        assert_eq!(
+            map.steps_between(|n| n == "11A" || n == "44A", |n| n.ends_with('Z')),
+            Err(NavigationError::NeverTogether)
+        );
+
+        let map = map.with_max_steps(1);
+        assert_eq!(map.steps_from("CCC", "ZZZ"), Ok(1));
+        assert_eq!(
+            map.steps_from("44A", "44Z"),
+            Err(NavigationError::TooManySteps(1))
+        );
+
+        // the walk stops right after the cap, before 44A gets round
+        let is_end = |id| map.name(id).ends_with('Z');
+        assert_eq!(
+            map.find_cycle(map.id("44A").unwrap(), is_end),
+            Cycle {
+                tail: 2,
+                length: 0,
+                hits: vec![]
+            }
+        );
+    }
+
+    #[test]
+    fn test_step_cap() {
+        // a single loop of 100001 nodes, with the end node at the far end
+        let mut input = "L\n\nA0 = (N1, N1)\n".to_string();
+        for i in 1..100_000 {
+            input += &format!("N{} = (N{}, N{})\n", i, i + 1, i + 1);
+        }
+        input += "N100000 = (ZZ, ZZ)\nZZ = (A0, A0)";
+        let map = input.parse::<Map>().unwrap();
+        assert_eq!(map.steps_from("A0", "ZZ"), Ok(100_001));
+
+        // with a cap, we stop walking soon after it
+        let map = map.with_max_steps(1000);
+        let cycle = map.find_cycle(map.id("A0").unwrap(), |_| false);
+        assert_eq!((cycle.tail, cycle.length), (1001, 0));
+        assert_eq!(
+            map.steps_from("A0", "ZZ").unwrap_err().to_string(),
+            "they're not all on an end node within 1000 steps"
+        );
+    }
+
+    #[test]
+    fn test_read_map() {
+        let input = "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+
+        let map = input.parse::<Map>().unwrap();
+
+        assert_eq!(
+            map.route,
+            vec![Instruction::Left, Instruction::Left, Instruction::Right]
+        );
+        assert_eq!(map.node_count(), 3);
+        assert_eq!(map.path("AAA"), Some(("BBB", "BBB")));
+        assert_eq!(map.path("BBB"), Some(("AAA", "ZZZ")));
+        assert_eq!(map.path("ZZZ"), Some(("ZZZ", "ZZZ")));
+        assert_eq!(map.path("CCC"), None);
+
+        // the nodes are numbered in the order they show up
+        let aaa = map.id("AAA").unwrap();
+        assert_eq!((aaa, map.id("ZZZ")), (0, Some(2)));
+        assert_eq!(map.name(aaa), "AAA");
+        assert_eq!(map.next(aaa, &Instruction::Right), map.id("BBB").unwrap());
+    }
+
+    #[test]
+    fn test_parse_route() {
+        assert_eq!(
+            parse_route("LLR"),
+            Ok(vec![
+                Instruction::Left,
+                Instruction::Left,
+                Instruction::Right
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse_route("LLX").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (3, "X"));
+
+        let err = "LR\n\nAAA = (BBB, BBB)\nBBB = BBB, ZZZ"
+            .parse::<Map>()
+            .unwrap_err();
+        assert_eq!((err.line, err.column), (4, 7));
+
+        // every node we go to needs a path of its own
+        let err = "LR\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, CCC)"
+            .parse::<Map>()
+            .unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 13, "ZZZ"));
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 13: expected a node with a path of its own, found 'ZZZ'"
+        );
+    }
+}
@@ -0,0 +1,589 @@
+use itertools::Itertools;
+use std::{fmt, str::FromStr};
+
+use crate::{grid::Grid, Answer, ParseError, Solution, SolveError};
+
+pub use crate::grid::Pos;
+
+/// Day 10
+///
+/// The input describes a loop of pipes. We have to find the longest distance
+/// from the start of the loop.
+///
+/// Based on the representation we choose, this can be calculated quite easily.
+/// If we manage to read the pipe network into a vec, then we're able to just
+/// take half of the length of the vec to get the farthest distance from the
+/// start.
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Pipe {
+    Start,
+    EastWest,
+    NorthSouth,
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+    Ground,
+    Outside,
+    Inside,
+}
+
+impl Pipe {
+    pub fn connects(&self, dir: Direction) -> bool {
+        match self {
+            Pipe::Start => true,
+            Pipe::Ground => false,
+            Pipe::Outside => false,
+            Pipe::Inside => false,
+            _ => match dir {
+                Direction::North => {
+                    matches!(self, Pipe::NorthSouth | Pipe::NorthEast | Pipe::NorthWest)
+                }
+                Direction::South => {
+                    matches!(self, Pipe::NorthSouth | Pipe::SouthEast | Pipe::SouthWest)
+                }
+                Direction::East => {
+                    matches!(self, Pipe::EastWest | Pipe::NorthEast | Pipe::SouthEast)
+                }
+                Direction::West => {
+                    matches!(self, Pipe::EastWest | Pipe::NorthWest | Pipe::SouthWest)
+                }
+            },
+        }
+    }
+
+    /// Given the direction we're going in when we enter this pipe, return
+    /// the direction we leave it in, or `None` if the pipe doesn't let us in
+    /// from that side
+    pub fn pass_from(&self, dir: Direction) -> Option<Direction> {
+        match dir {
+            Direction::North => match self {
+                Pipe::SouthEast => Some(Direction::East),
+                Pipe::SouthWest => Some(Direction::West),
+                Pipe::NorthSouth => Some(Direction::North),
+                _ => None,
+            },
+            Direction::South => match self {
+                Pipe::NorthEast => Some(Direction::East),
+                Pipe::NorthWest => Some(Direction::West),
+                Pipe::NorthSouth => Some(Direction::South),
+                _ => None,
+            },
+            Direction::East => match self {
+                Pipe::NorthWest => Some(Direction::North),
+                Pipe::SouthWest => Some(Direction::South),
+                Pipe::EastWest => Some(Direction::East),
+                _ => None,
+            },
+            Direction::West => match self {
+                Pipe::NorthEast => Some(Direction::North),
+                Pipe::SouthEast => Some(Direction::South),
+                Pipe::EastWest => Some(Direction::West),
+                _ => None,
+            },
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    /// The directions in the order we look around the start
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// The (x, y) offset of a step in this direction
+    pub fn offset(&self) -> (isize, isize) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::South => (0, 1),
+            Direction::East => (1, 0),
+            Direction::West => (-1, 0),
+        }
+    }
+
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+        }
+    }
+}
+
+// impl TryFrom<char> for Pipe, handing back the char we don't know
+impl TryFrom<char> for Pipe {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Ok(match c {
+            'S' => Pipe::Start,
+            '-' => Pipe::EastWest,
+            '|' => Pipe::NorthSouth,
+            'L' => Pipe::NorthEast,
+            'J' => Pipe::NorthWest,
+            '7' => Pipe::SouthWest,
+            'F' => Pipe::SouthEast,
+            '.' => Pipe::Ground,
+            'O' => Pipe::Outside,
+            'I' => Pipe::Inside,
+            _ => return Err(c),
+        })
+    }
+}
+
+// impl Into<char> for Pipe
+impl From<Pipe> for char {
+    fn from(val: Pipe) -> Self {
+        match val {
+            Pipe::Start => 'S',
+            Pipe::EastWest => '─',
+            Pipe::NorthSouth => '│',
+            Pipe::NorthEast => '└',
+            Pipe::NorthWest => '┘',
+            Pipe::SouthWest => '┐',
+            Pipe::SouthEast => '┌',
+            Pipe::Ground => '·',
+            Pipe::Outside => 'O',
+            Pipe::Inside => 'I',
+        }
+    }
+}
+
+impl fmt::Display for Pipe {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", char::from(*self))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Map(Grid<Pipe>);
+
+impl FromStr for Map {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = Grid::parse_with(s, "a pipe or ground", |c| Pipe::try_from(c).ok())?;
+
+        // the loop starts at the one and only 'S'
+        let mut starts = s.lines().enumerate().flat_map(|(y, line)| {
+            line.match_indices('S')
+                .map(move |(_, token)| (y, line, token))
+        });
+        if starts.next().is_none() {
+            let (y, line) = s.lines().enumerate().last().unwrap_or((0, ""));
+            return Err(ParseError::end_of(line, "an 'S' to start from").offset(y));
+        }
+        if let Some((y, line, token)) = starts.next() {
+            return Err(ParseError::new(line, token, "only one 'S'").offset(y));
+        }
+
+        Ok(Map(grid))
+    }
+}
+
+impl fmt::Display for Map {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Map {
+    /// Find the first pipe next to `pos` that connects back to it, looking
+    /// up, right, down and left in that order
+    pub fn find_a_start(&self, pos: Pos) -> Option<(Direction, Pos)> {
+        Direction::ALL.into_iter().find_map(|dir| {
+            let next = self.0.offset(pos, dir.offset())?;
+            self.0[next].connects(dir.opposite()).then_some((dir, next))
+        })
+    }
+
+    /// Is the pipe next to `pos` in a direction connected back to it?
+    fn connected(&self, pos: Pos, dir: Direction) -> bool {
+        self.0
+            .offset(pos, dir.offset())
+            .is_some_and(|next| self.0[next].connects(dir.opposite()))
+    }
+
+    /// When we have that 2d grid, we can find the loop that's inside of it.
+    /// We need to start from the 'S', and find at least one neighbouring pipe.
+    /// Then, keep following pipes until we find an S again.
+    /// After that, we should have the length of the loop.
+    ///
+    /// Returns `None` if there's no start, or the pipes from it don't lead
+    /// back to it.
+    pub fn loop_length(&self) -> Option<usize> {
+        self.loop_positions().map(|positions| positions.len())
+    }
+
+    pub fn loop_positions(&self) -> Option<Vec<Pos>> {
+        let start_pos = self.start()?;
+        let mut positions = vec![start_pos];
+        let (mut direction, mut pos) = self.find_a_start(start_pos)?;
+        // from this point on, we can follow the pipes until we
+        // find the start again
+        while pos != start_pos {
+            positions.push(pos);
+            // get the new direction based on the current pipe, and take a
+            // step that way. Either can fail when the loop is broken.
+            direction = self.0[pos].pass_from(direction)?;
+            pos = self.0.offset(pos, direction.offset())?;
+        }
+        Some(positions)
+    }
+
+    /// get the pipe at the given position
+    pub fn get_pipe(&self, x: usize, y: usize) -> Option<Pipe> {
+        self.0.get((x, y)).copied()
+    }
+
+    pub fn set_pipe(&mut self, x: usize, y: usize, pipe: Pipe) {
+        self.0[(x, y)] = pipe;
+    }
+
+    /// find the start position
+    pub fn start(&self) -> Option<Pos> {
+        self.0.position(|&c| c == Pipe::Start)
+    }
+
+    pub fn nr_inside(&self) -> usize {
+        self.0.iter().filter(|(_, &c)| c == Pipe::Inside).count()
+    }
+
+    /// Mark the tiles inside and outside of the loop. Returns `None`, and
+    /// leaves the map alone, if there's no loop to go by.
+    pub fn mark_inside(&mut self) -> Option<()> {
+        // first we need to change everything that's not part of the loop, into
+        // ground
+        let loop_positions = self.loop_positions()?;
+
+        for (pos, pipe) in self.0.iter_mut() {
+            if !loop_positions.contains(&pos) {
+                *pipe = Pipe::Ground;
+            }
+        }
+        // We need to iterate over tiles within the bounds of the loop.
+        // A stripwise approach could work fine, as long as we keep track
+        // whether we're inside, outside, or on the border. And, we should keep
+        // track of the direction of outside, since this could help us deter-
+        // mine if we end up inside after corners.
+        //
+        // There's a few possibilities of pipes we can encounter on a line.
+        // |...|      -> easy, we go inside and outside again.
+        // |..|..|..| -> inside, outside, inside
+        // F---7..... -> remain outside
+        // L---J..... -> remain outside
+        // |..F-7...| -> inside, outside, inside
+        // |..L-7...| -> inside, outside
+        // FJ...LJ.L7 -> inside, inside
+        //
+        // Basically, if we keep track of the last corner, we know if we have
+        // crossed inside or outside.
+
+        // group the positions of the loop into a hashmap with y as key
+        let mut v_pipe_groups = std::collections::HashMap::new();
+        for &(x, y) in &loop_positions {
+            v_pipe_groups.entry(y).or_insert(Vec::new()).push(x);
+        }
+
+        // loop from the lowest x+1 to the highest x-1
+        for (y, x_positions) in v_pipe_groups {
+            let (&min_x, &max_x) = x_positions.iter().minmax().into_option()?;
+            // keep track of whether we're inside or outside
+            let mut inside = false;
+            // Keep track of the last corner and initialize it as ground to
+            // indicate it's not a corner
+            let mut last_corner = Pipe::Ground;
+            for x in min_x..=max_x {
+                let mut tile = self.0[(x, y)];
+                if tile == Pipe::Start {
+                    tile = self.deduct_pipe(x, y);
+                }
+                match tile {
+                    Pipe::Ground => {
+                        if inside {
+                            self.set_pipe(x, y, Pipe::Inside);
+                        } else {
+                            self.set_pipe(x, y, Pipe::Outside);
+                        }
+                    }
+                    Pipe::NorthSouth => inside = !inside,
+                    Pipe::NorthEast => last_corner = Pipe::NorthEast,
+                    Pipe::NorthWest if last_corner == Pipe::SouthEast => {
+                        inside = !inside;
+                    }
+                    Pipe::SouthEast => last_corner = Pipe::SouthEast,
+                    Pipe::SouthWest if last_corner == Pipe::NorthEast => {
+                        inside = !inside;
+                    }
+                    _ => (),
+                }
+            }
+        }
+        Some(())
+    }
+
+    pub fn width(&self) -> usize {
+        self.0.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.0.height()
+    }
+
+    /// Deduct what pipe is at x, y by looking at the surrounding pipes
+    pub fn deduct_pipe(&self, x: usize, y: usize) -> Pipe {
+        let connected_above = self.connected((x, y), Direction::North);
+        let connected_below = self.connected((x, y), Direction::South);
+        let connected_left = self.connected((x, y), Direction::West);
+        let connected_right = self.connected((x, y), Direction::East);
+
+        if connected_above && connected_below {
+            Pipe::NorthSouth
+        } else if connected_left && connected_right {
+            Pipe::EastWest
+        } else if connected_above && connected_left {
+            Pipe::NorthWest
+        } else if connected_above && connected_right {
+            Pipe::NorthEast
+        } else if connected_below && connected_left {
+            Pipe::SouthWest
+        } else if connected_below && connected_right {
+            Pipe::SouthEast
+        } else {
+            Pipe::Ground
+        }
+    }
+}
+
+pub fn solve(input: &str) -> usize {
+    let map = Map::from_str(input).expect("Failed to parse map");
+    map.loop_length().expect("Failed to find a loop") / 2
+}
+
+pub fn solve2(input: &str) -> usize {
+    let mut map = Map::from_str(input).expect("Failed to parse map");
+    map.mark_inside().expect("Failed to find a loop");
+    map.nr_inside()
+}
+
+/// Why a map has no loop we can measure
+fn no_loop(map: &Map) -> SolveError {
+    match map.start() {
+        None => SolveError::new("there's no 'S' on the map"),
+        Some((x, y)) => SolveError::new(format!(
+            "the pipes from the start at ({}, {}) don't form a loop",
+            x, y
+        )),
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Map;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Map::from_str(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError> {
+        let length = input.loop_length().ok_or_else(|| no_loop(input))?;
+        Ok((length / 2).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError> {
+        // marking the inside tiles changes the map, so we work on a copy
+        let mut map = input.clone();
+        map.mark_inside().ok_or_else(|| no_loop(input))?;
+        Ok(map.nr_inside().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(
+            4,
+            solve(
+                r#".....
+.S-7.
+.|.|.
+.L-J.
+....."#
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Map::from_str(".....\n.S-7.\n.|x|.").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 3, "x"));
+    }
+
+    #[test]
+    fn test_parse_needs_one_start() {
+        let err = Map::from_str("..\n..").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, ""));
+        assert_eq!(err.expected, "an 'S' to start from");
+
+        let err = Map::from_str(".S-7.\n.|.|.\n.L-JS").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 5, "S"));
+        assert_eq!(err.expected, "only one 'S'");
+    }
+
+    #[test]
+    fn test_broken_loop() {
+        for input in [".S-\n...", "S7\n||", "S..\n...", ".S-7.\n.|.|.\n.L-|."] {
+            let mut map = Map::from_str(input).unwrap();
+            assert_eq!(None, map.loop_length(), "{}", input);
+            assert_eq!(None, map.mark_inside(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_no_loop_errors() {
+        let map = Day10::parse("S7\n||").unwrap();
+        let broken = SolveError::new("the pipes from the start at (0, 0) don't form a loop");
+        assert_eq!(Err(broken.clone()), Day10::part1(&map));
+        assert_eq!(Err(broken), Day10::part2(&map));
+
+        // the parser won't let a map without a start through, but it can
+        // still lose it afterwards
+        let mut map = Day10::parse(".S-7.\n.|.|.\n.L-J.").unwrap();
+        map.set_pipe(1, 0, Pipe::SouthEast);
+        let no_start = SolveError::new("there's no 'S' on the map");
+        assert_eq!(Err(no_start.clone()), Day10::part1(&map));
+        assert_eq!(Err(no_start), Day10::part2(&map));
+    }
+
+    #[test]
+    fn test_start_pos() {
+        let map = Map::from_str(
+            r#".....
+.S-7.
+.|.|.
+.L-J.
+....."#,
+        )
+        .expect("Failed to parse map");
+        assert_eq!(Some((1, 1)), map.start());
+    }
+
+    #[test]
+    fn test_loop_length() {
+        let map = Map::from_str(
+            r#".....
+.S-7.
+.|.|.
+.L-J.
+....."#,
+        )
+        .expect("Failed to parse map");
+        assert_eq!(Some(8), map.loop_length());
+    }
+
+    #[test]
+    fn test_count_inside() {
+        let map = Map::from_str(
+            r#"..........
+.S------7.
+.|F----7|.
+.||OOOO||.
+.||OOOO||.
+.|L-7F-J|.
+.|II||II|.
+.L--JL--J.
+.........."#,
+        )
+        .expect("Failed to parse map");
+        assert_eq!(4, map.nr_inside());
+    }
+
+    #[test]
+    fn test_solve2() {
+        assert_eq!(
+            4,
+            solve2(
+                r#"..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+.........."#
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve2_start_problem() {
+        assert_eq!(
+            4,
+            solve2(
+                r#"..........
+.F------7.
+.|F----7|.
+.||....||.
+.S|....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+.........."#
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve2b() {
+        assert_eq!(
+            11,
+            solve2(
+                r#"..........
+.S-7......
+.|.L-7....
+.|...L--7.
+.|..F-7.|.
+.|.FJ.|.|.
+.|.|..|.|.
+.L-J..L-J.
+.........."#
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve2c() {
+        assert_eq!(
+            10,
+            solve2(
+                r#"FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJIF7FJ-
+L---JF-JLJIIIIFJLJJ7
+|F|F-JF---7IIIL7L|7|
+|FFJF7L7F-JF7IIL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L"#
+            )
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use crate::ParseError;
+
+/// A position on a grid, as (x, y) with (0, 0) in the top left corner
+pub type Pos = (usize, usize);
+
+/// The offsets to the 4 direct neighbours: up, right, down and left
+const NEIGHBOURS4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// The offsets to all 8 neighbours, row by row
+const NEIGHBOURS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular 2D grid, stored row by row
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Parse a grid of characters, turning every character into a cell with
+    /// `f`. Characters `f` doesn't accept are reported as not being
+    /// `expected`, and all lines should be as long as the first one.
+    pub fn parse_with(
+        s: &str,
+        expected: &str,
+        f: impl Fn(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let width = s.lines().next().map_or(0, |line| line.chars().count());
+        let mut cells = vec![];
+        let mut height = 0;
+
+        for (y, line) in s.lines().enumerate() {
+            let mut count = 0;
+            for (i, c) in line.char_indices() {
+                let token = &line[i..i + c.len_utf8()];
+                if count == width {
+                    return Err(ParseError::new(line, token, "end of line").offset(y));
+                }
+                cells.push(f(c).ok_or_else(|| ParseError::new(line, token, expected).offset(y))?);
+                count += 1;
+            }
+            if count < width {
+                return Err(ParseError::end_of(line, format!("{} cells", width)).offset(y));
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get the cell at a position, or None if it's outside of the grid
+    pub fn get(&self, (x, y): Pos) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Get the cell at a position mutably, or None if it's outside of the grid
+    pub fn get_mut(&mut self, (x, y): Pos) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Move from a position by an offset, if we stay on the grid
+    pub fn offset(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let x = x.checked_add_signed(dx).filter(|&x| x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|&y| y < self.height)?;
+        Some((x, y))
+    }
+
+    /// The up to 4 direct neighbours of a position that are on the grid
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS4.iter().filter_map(move |&d| self.offset(pos, d))
+    }
+
+    /// The up to 8 neighbours of a position that are on the grid, including
+    /// the diagonal ones
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS8.iter().filter_map(move |&d| self.offset(pos, d))
+    }
+
+    /// Iterate over the rows, top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks doesn't allow a chunk size of 0, and an empty grid has no
+        // rows anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Iterate over the cells in a single column, top to bottom
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells
+            .iter()
+            .skip(x)
+            .step_by(self.width.max(1))
+            .take(if x < self.width { self.height } else { 0 })
+    }
+
+    /// Iterate over the columns, left to right
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Iterate over every cell with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, c)| ((i % width, i / width), c))
+    }
+
+    /// Iterate mutably over every cell with its position, row by row
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Pos, &mut T)> {
+        let width = self.width;
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, c)| ((i % width, i / width), c))
+    }
+
+    /// Find the position of the first cell that matches, row by row
+    pub fn position(&self, f: impl Fn(&T) -> bool) -> Option<Pos> {
+        self.iter().find(|(_, c)| f(c)).map(|(pos, _)| pos)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", pos))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", pos))
+    }
+}
+
+/// Read a grid of any cell that can be made from a character
+impl<T: TryFrom<char>> FromStr for Grid<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse_with(s, "a grid cell", |c| T::try_from(c).ok())
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get((2, 1)), Some(&'f'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let digit = |c: char| c.to_digit(10);
+        let err = Grid::parse_with("12\n3x", "a digit", digit).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, "x"));
+
+        let err = Grid::parse_with("12\n3", "a digit", digit).unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (2, 2, "2 cells")
+        );
+
+        let err = Grid::parse_with("12\n345", "a digit", digit).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, "5"));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = grid();
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours4((1, 1)).count(), 3);
+        assert_eq!(grid.neighbours8((1, 0)).count(), 5);
+        assert_eq!(
+            grid.neighbours8((2, 1)).collect::<Vec<_>>(),
+            vec![(1, 0), (2, 0), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = grid();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&['a', 'b', 'c'], &['d', 'e', 'f']]
+        );
+        let columns = grid
+            .columns()
+            .map(|c| c.collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(columns, vec!["ad", "be", "cf"]);
+        assert_eq!(grid.column(3).count(), 0);
+    }
+
+    #[test]
+    fn test_index() {
+        let mut grid = grid();
+        grid[(1, 1)] = 'x';
+        assert_eq!(grid[(1, 1)], 'x');
+        assert_eq!(grid.position(|&c| c == 'x'), Some((1, 1)));
+        assert_eq!(grid.iter().nth(4), Some(((1, 1), &'x')));
+    }
+
+    #[test]
+    fn test_empty() {
+        let grid = "".parse::<Grid<char>>().unwrap();
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.to_string(), "");
+    }
+}
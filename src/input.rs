@@ -0,0 +1,126 @@
+use std::{
+    env, fmt, fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+/// The environment variable that points to a directory with puzzle inputs
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// The directory we read inputs from when nothing else is given
+pub const DEFAULT_INPUT_DIR: &str = "input";
+
+/// Where the input for a day comes from
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    /// Resolve where to read the input for a day from. In order, we look at:
+    /// 1. the path given with `--input`, where `-` means stdin
+    /// 2. the `AOC_INPUT_DIR` environment variable
+    /// 3. the `input/` directory
+    pub fn resolve(day: u32, path: Option<&str>) -> Source {
+        match path {
+            Some("-") => Source::Stdin,
+            Some(path) => Source::File(path.into()),
+            None => {
+                let dir = env::var_os(INPUT_DIR_VAR)
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| DEFAULT_INPUT_DIR.into());
+                Source::File(dir.join(format!("day{:02}.txt", day)))
+            }
+        }
+    }
+
+    /// Read the input from this source
+    pub fn read(&self) -> Result<String, InputError> {
+        match self {
+            Source::File(path) => fs::read_to_string(path).map_err(|e| match e.kind() {
+                io::ErrorKind::NotFound => InputError::NotFound(path.clone()),
+                _ => InputError::Io(self.clone(), e),
+            }),
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|e| InputError::Io(self.clone(), e))?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    NotFound(PathBuf),
+    Io(Source, io::Error),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::NotFound(path) => write!(
+                f,
+                "input file '{}' not found, pass one with --input or set {}",
+                path.display(),
+                INPUT_DIR_VAR
+            ),
+            InputError::Io(source, e) => write!(f, "failed to read input from {}: {}", source, e),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Load the input for a day, for the single day binaries. These only take an
+/// optional `--input <path>` argument, and exit on errors.
+pub fn load_from_args(day: u32) -> String {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let path = match args.as_slice() {
+        [] => None,
+        [flag, path] if flag == "--input" || flag == "-i" => Some(path.as_str()),
+        _ => {
+            eprintln!("Usage: day{:02} [--input <path>]", day);
+            std::process::exit(2);
+        }
+    };
+
+    Source::resolve(day, path).read().unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_explicit() {
+        assert_eq!(
+            Source::resolve(5, Some("other.txt")),
+            Source::File("other.txt".into())
+        );
+        assert_eq!(Source::resolve(5, Some("-")), Source::Stdin);
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let source = Source::File("input/day99.txt".into());
+        let err = source.read().unwrap_err();
+        assert!(matches!(err, InputError::NotFound(_)));
+        assert!(err.to_string().contains("input/day99.txt"));
+    }
+}
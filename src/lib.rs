@@ -0,0 +1,12 @@
+pub mod answers;
+pub mod days;
+pub mod grid;
+pub mod input;
+pub mod math;
+pub mod parse;
+pub mod ranges;
+pub mod runner;
+pub mod solution;
+
+pub use parse::ParseError;
+pub use solution::{Answer, Solution, SolveError};
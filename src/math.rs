@@ -0,0 +1,267 @@
+use std::fmt;
+
+/// The greatest common divisor of two numbers
+pub fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple of two numbers, or None if it doesn't fit in a
+/// u128. The lcm with 0 is 0.
+pub fn checked_lcm(a: u128, b: u128) -> Option<u128> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// The least common multiple of all numbers, or None if it doesn't fit. The
+/// lcm of no numbers at all is 1.
+pub fn checked_lcm_all(nums: impl IntoIterator<Item = u128>) -> Option<u128> {
+    nums.into_iter().try_fold(1, checked_lcm)
+}
+
+/// Find gcd(a, b) together with p and q so that a * p + b * q = gcd(a, b).
+/// The gcd is never negative.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_p, mut p) = (1, 0);
+    let (mut old_q, mut q) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_p, p) = (p, old_p - quotient * p);
+        (old_q, q) = (q, old_q - quotient * q);
+    }
+    if old_r < 0 {
+        (-old_r, -old_p, -old_q)
+    } else {
+        (old_r, old_p, old_q)
+    }
+}
+
+/// (a * b) % m without overflowing, by adding up a doubled step by step
+fn mul_mod(mut a: u128, mut b: u128, m: u128) -> u128 {
+    a %= m;
+    let mut result = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b >>= 1;
+    }
+    result
+}
+
+/// (a + b) % m for a and b below m, without overflowing
+fn add_mod(a: u128, b: u128, m: u128) -> u128 {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// x ≡ remainder (mod modulus)
+///
+/// With a modulus of 0 that means x is the remainder, and nothing else.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Congruence {
+    pub remainder: u128,
+    pub modulus: u128,
+}
+
+impl Congruence {
+    /// A congruence with the remainder brought below the modulus
+    pub fn new(remainder: u128, modulus: u128) -> Congruence {
+        Congruence {
+            remainder: if modulus == 0 {
+                remainder
+            } else {
+                remainder % modulus
+            },
+            modulus,
+        }
+    }
+
+    /// The lowest x that is at least `min` and fits the congruence, or None
+    /// if that doesn't fit in a u128
+    pub fn first_from(&self, min: u128) -> Option<u128> {
+        if self.remainder >= min {
+            return Some(self.remainder);
+        }
+        if self.modulus == 0 {
+            return None;
+        }
+        let times = (min - self.remainder).div_ceil(self.modulus);
+        times.checked_mul(self.modulus)?.checked_add(self.remainder)
+    }
+}
+
+impl fmt::Display for Congruence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "x ≡ {} (mod {})", self.remainder, self.modulus)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CrtError {
+    /// No number fits both congruences
+    NoSolution(Congruence, Congruence),
+    /// A modulus of 0
+    ZeroModulus,
+    /// The combined modulus doesn't fit in a u128 (or the moduli are too big
+    /// to work with)
+    Overflow,
+}
+
+impl fmt::Display for CrtError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CrtError::NoSolution(a, b) => write!(f, "no number fits both {} and {}", a, b),
+            CrtError::ZeroModulus => write!(f, "a modulus can't be 0"),
+            CrtError::Overflow => write!(f, "the combined modulus is too big"),
+        }
+    }
+}
+
+impl std::error::Error for CrtError {}
+
+/// Combine two congruences into one that holds exactly when both do. The
+/// moduli don't have to be coprime, as long as the remainders agree on what
+/// the moduli have in common.
+pub fn crt_pair(a: Congruence, b: Congruence) -> Result<Congruence, CrtError> {
+    let (m, n) = (a.modulus, b.modulus);
+    if m == 0 || n == 0 {
+        return Err(CrtError::ZeroModulus);
+    }
+    let (a, b) = (
+        Congruence::new(a.remainder, m),
+        Congruence::new(b.remainder, n),
+    );
+
+    let signed = |v: u128| i128::try_from(v).map_err(|_| CrtError::Overflow);
+    let (g, p, _) = extended_gcd(signed(m)?, signed(n)?);
+    let g = g as u128;
+    let diff = signed(b.remainder)? - signed(a.remainder)?;
+    if diff % g as i128 != 0 {
+        return Err(CrtError::NoSolution(a, b));
+    }
+
+    // (m / g) * p ≡ 1 (mod n / g), so a + m * k with k ≡ p * diff / g
+    // (mod n / g) fits both
+    let step = n / g;
+    let lcm = (m / g).checked_mul(n).ok_or(CrtError::Overflow)?;
+    let diff = (diff / g as i128).rem_euclid(step as i128) as u128;
+    let p = p.rem_euclid(step as i128) as u128;
+    let k = mul_mod(diff, p, step);
+    // m * k < m * (n / g) = lcm, so this fits
+    let remainder = add_mod(a.remainder, m * k, lcm);
+    Ok(Congruence {
+        remainder,
+        modulus: lcm,
+    })
+}
+
+/// Combine any number of congruences into one, with the generalized Chinese
+/// remainder theorem. No congruences at all means any number will do.
+pub fn crt(congruences: impl IntoIterator<Item = Congruence>) -> Result<Congruence, CrtError> {
+    congruences
+        .into_iter()
+        .try_fold(Congruence::new(0, 1), crt_pair)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(checked_lcm(4, 6), Some(12));
+        assert_eq!(checked_lcm(0, 6), Some(0));
+        assert_eq!(checked_lcm_all([2, 3, 4]), Some(12));
+        assert_eq!(checked_lcm_all([]), Some(1));
+        assert_eq!(checked_lcm(u128::MAX, u128::MAX - 1), None);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        let (g, p, q) = extended_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * p + 46 * q, 2);
+        assert_eq!(extended_gcd(-4, 6).0, 2);
+    }
+
+    #[test]
+    fn test_crt() {
+        // coprime
+        let c = crt([
+            Congruence::new(2, 3),
+            Congruence::new(3, 5),
+            Congruence::new(2, 7),
+        ]);
+        assert_eq!(c, Ok(Congruence::new(23, 105)));
+
+        // not coprime, but the remainders agree mod 2
+        assert_eq!(
+            crt([Congruence::new(4, 6), Congruence::new(6, 8)]),
+            Ok(Congruence::new(22, 24))
+        );
+        assert_eq!(
+            crt([Congruence::new(1, 6), Congruence::new(2, 8)]),
+            Err(CrtError::NoSolution(
+                Congruence::new(1, 6),
+                Congruence::new(2, 8)
+            ))
+        );
+        assert_eq!(crt([Congruence::new(1, 0)]), Err(CrtError::ZeroModulus));
+        assert_eq!(crt([]), Ok(Congruence::new(0, 1)));
+
+        // big moduli that only just fit
+        let big = 1u128 << 100;
+        assert_eq!(
+            crt([Congruence::new(1, big), Congruence::new(0, 3)]),
+            Ok(Congruence::new(2 * big + 1, 3 * big))
+        );
+        assert_eq!(
+            crt([Congruence::new(1, big), Congruence::new(0, big - 1)]),
+            Err(CrtError::Overflow)
+        );
+
+        assert_eq!(Congruence::new(22, 24).first_from(30), Some(46));
+        assert_eq!(Congruence::new(22, 24).first_from(5), Some(22));
+        assert_eq!(Congruence::new(7, 0).first_from(5), Some(7));
+        assert_eq!(Congruence::new(7, 0).first_from(8), None);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_crt(congruences in prop::collection::vec((0u128..50, 1u128..12), 1..4)) {
+            let congruences = congruences
+                .into_iter()
+                .map(|(r, m)| Congruence::new(r, m))
+                .collect::<Vec<_>>();
+            let fits = |x: u128| congruences.iter().all(|c| x % c.modulus == c.remainder);
+            let lcm = checked_lcm_all(congruences.iter().map(|c| c.modulus)).unwrap();
+            let expected = (0..lcm).find(|&x| fits(x));
+
+            match crt(congruences.iter().copied()) {
+                Ok(c) => {
+                    prop_assert_eq!(c.modulus, lcm);
+                    prop_assert_eq!(Some(c.remainder), expected);
+                }
+                Err(e) => {
+                    prop_assert!(matches!(e, CrtError::NoSolution(..)));
+                    prop_assert_eq!(expected, None);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use std::{fmt, str::FromStr};
+
+/// An error while parsing the puzzle input, pointing at the offending text.
+///
+/// Lines and columns start at 1, like in an editor.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Create an error for `token`, which should be a slice of `line`. The
+    /// column is taken from where the token sits in the line.
+    pub fn new(line: &str, token: &str, expected: impl Into<String>) -> Self {
+        ParseError {
+            line: 1,
+            column: column_of(line, token),
+            text: token.to_owned(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Create an error for something that's missing at the end of `line`
+    pub fn end_of(line: &str, expected: impl Into<String>) -> Self {
+        ParseError::new(line, &line[line.len()..], expected)
+    }
+
+    /// Move the error down by a number of lines. Parsers that only see a
+    /// single line or block report line 1, the caller knows where that line
+    /// is in the whole input.
+    pub fn offset(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+
+    /// Render the error together with the line it happened on, and a caret
+    /// under the offending text.
+    pub fn render(&self, input: &str) -> String {
+        let source = input.lines().nth(self.line - 1).unwrap_or("");
+        let gutter = self.line.to_string().len();
+        let width = self.text.chars().count().max(1);
+        format!(
+            "{}\n{:gutter$} |\n{} | {}\n{:gutter$} | {}{}",
+            self,
+            "",
+            self.line,
+            source,
+            "",
+            " ".repeat(self.column - 1),
+            "^".repeat(width),
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let found = if self.text.is_empty() {
+            "end of line".to_owned()
+        } else {
+            format!("'{}'", self.text)
+        };
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.column, self.expected, found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Find the column of `token` in `line`. If the token isn't a slice of the
+/// line, we fall back to searching for it.
+fn column_of(line: &str, token: &str) -> usize {
+    let start = line.as_ptr() as usize;
+    let pos = token.as_ptr() as usize;
+    let offset = if pos >= start && pos <= start + line.len() {
+        pos - start
+    } else {
+        line.find(token).unwrap_or(0)
+    };
+    line[..offset].chars().count() + 1
+}
+
+/// Parse `token` (a slice of `line`) as a number
+pub fn number<T: FromStr>(line: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::new(line, token, "a number"))
+}
+
+/// Parse every line of the input with `f`, putting the right line number on
+/// any error.
+pub fn lines<T>(
+    input: &str,
+    f: impl Fn(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| f(line).map_err(|e| e.offset(i)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column() {
+        let line = "Card 1: 41 4x 83";
+        let token = line.split_whitespace().nth(3).unwrap();
+        let err = ParseError::new(line, token, "a number");
+        assert_eq!(err.column, 12);
+        assert_eq!(err.text, "4x");
+    }
+
+    #[test]
+    fn test_lines() {
+        let err = lines("1 2\n3 x\n", |line| {
+            line.split(' ')
+                .map(|n| number::<u32>(line, n))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+
+    #[test]
+    fn test_render() {
+        let err = number::<u32>("1 2 abc", &"1 2 abc"[4..])
+            .unwrap_err()
+            .offset(1);
+        assert_eq!(
+            err.render("first\n1 2 abc\nlast"),
+            "line 2, column 5: expected a number, found 'abc'
+  |
+2 | 1 2 abc
+  |     ^^^"
+        );
+    }
+}
@@ -0,0 +1,542 @@
+use std::{
+    cmp::{max, min},
+    fmt,
+    ops::{Add, RangeInclusive, Sub},
+};
+
+/// Values that come one after another, like the integers. Ranges of them
+/// include their end, so a range can go right up to the largest value.
+pub trait Discrete: Ord + Copy {
+    /// The value right after this one, or None for the largest value
+    fn succ(self) -> Option<Self>;
+
+    /// The value right before this one, or None for the smallest value
+    fn pred(self) -> Option<Self>;
+}
+
+macro_rules! impl_discrete {
+    ($($t:ty),*) => {
+        $(impl Discrete for $t {
+            fn succ(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            fn pred(self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+        })*
+    };
+}
+
+impl_discrete!(u8, u16, u32, u64, u128, usize);
+
+/// A set of values, kept as sorted ranges that don't overlap or touch.
+///
+/// Every operation hands back a normalised set, so two sets with the same
+/// values always compare equal.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RangeSet<T> {
+    ranges: Vec<RangeInclusive<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        RangeSet { ranges: vec![] }
+    }
+}
+
+impl<T: Discrete> RangeSet<T> {
+    pub fn new() -> Self {
+        RangeSet::default()
+    }
+
+    /// Sort the ranges, drop the empty ones and merge the ones that overlap
+    /// or touch
+    pub fn normalise(mut ranges: Vec<RangeInclusive<T>>) -> Vec<RangeInclusive<T>> {
+        coalesce(&mut ranges);
+        ranges
+    }
+
+    pub fn ranges(&self) -> &[RangeInclusive<T>] {
+        &self.ranges
+    }
+
+    pub fn into_ranges(self) -> Vec<RangeInclusive<T>> {
+        self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The lowest value in the set
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| *r.start())
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        // find the last range that starts at or before the value
+        let i = self.ranges.partition_point(|r| *r.start() <= value);
+        i > 0 && value <= *self.ranges[i - 1].end()
+    }
+
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        *self = self.union(&RangeSet::from(range));
+    }
+
+    /// All values that are in either set
+    pub fn union(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        self.ranges
+            .iter()
+            .chain(other.ranges.iter())
+            .cloned()
+            .collect()
+    }
+
+    /// All values that are in both sets
+    pub fn intersection(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut out = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let start = max(*a.start(), *b.start());
+            let end = min(*a.end(), *b.end());
+            if start <= end {
+                out.push(start..=end);
+            }
+            // move past whichever range ends first
+            if a.end() < b.end() {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        // the pieces are sorted and can't touch, since the inputs don't
+        RangeSet { ranges: out }
+    }
+
+    /// All values in this set that are not in the other set
+    pub fn difference(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut out = vec![];
+        let mut j = 0;
+        for r in &self.ranges {
+            // the first value we haven't handled yet, None once we're past
+            // the largest value
+            let mut start = Some(*r.start());
+            // skip the ranges of other that end before this range
+            while j < other.ranges.len() && other.ranges[j].end() < r.start() {
+                j += 1;
+            }
+            // cut out every range of other that starts inside this one
+            let mut k = j;
+            while let Some(s) = start.filter(|_| k < other.ranges.len()) {
+                let hole = &other.ranges[k];
+                if hole.start() > r.end() {
+                    break;
+                }
+                if let Some(before) = hole.start().pred().filter(|&b| b >= s) {
+                    out.push(s..=before);
+                }
+                start = if *hole.end() < s {
+                    Some(s)
+                } else {
+                    hole.end().succ()
+                };
+                k += 1;
+            }
+            if let Some(s) = start.filter(|s| s <= r.end()) {
+                out.push(s..=*r.end());
+            }
+        }
+        RangeSet { ranges: out }
+    }
+}
+
+impl<T: Discrete + Add<Output = T> + Sub<Output = T>> RangeSet<T> {
+    /// Move every value `x` to `to + (x - from)`. All values should be at
+    /// least `from`.
+    pub fn shift(&self, from: T, to: T) -> RangeSet<T> {
+        let ranges = self
+            .ranges
+            .iter()
+            .map(|r| to + (*r.start() - from)..=to + (*r.end() - from))
+            .collect();
+        // shifting everything by the same amount keeps the set normalised
+        RangeSet { ranges }
+    }
+
+    /// Map the set through a piecewise-affine function. Every piece is a
+    /// source range and the value its start moves to. When pieces overlap the
+    /// first one wins, and values outside of all pieces stay where they are.
+    pub fn map_affine(
+        &self,
+        pieces: impl IntoIterator<Item = (RangeInclusive<T>, T)>,
+    ) -> RangeSet<T> {
+        // cut every piece down to what the pieces before it leave over, so
+        // they don't overlap any more and can be swept through in order
+        let mut covered = RangeSet::new();
+        let mut disjoint = vec![];
+        for (src, dst) in pieces {
+            let free = RangeSet::from(src.clone()).difference(&covered);
+            disjoint.extend(
+                free.ranges
+                    .iter()
+                    .map(|r| (r.clone(), dst + (*r.start() - *src.start()))),
+            );
+            covered.insert(src);
+        }
+        disjoint.sort_by_key(|(r, _)| *r.start());
+
+        let mut out = Coalescer::new();
+        map_affine_into(&self.ranges, disjoint, &mut out);
+        out.into_set()
+    }
+}
+
+/// Map ranges through a piecewise-affine function like `map_affine` does,
+/// pushing the mapped pieces onto `out`. Both the ranges and the pieces have
+/// to be sorted by start without overlaps, so we can walk through them
+/// together.
+pub fn map_affine_into<T: Discrete + Add<Output = T> + Sub<Output = T>>(
+    ranges: &[RangeInclusive<T>],
+    pieces: impl IntoIterator<Item = (RangeInclusive<T>, T)>,
+    out: &mut Coalescer<T>,
+) {
+    let mut pieces = pieces.into_iter().peekable();
+    for r in ranges.iter().filter(|r| !r.is_empty()) {
+        let mut start = *r.start();
+        loop {
+            while pieces.next_if(|(src, _)| *src.end() < start).is_some() {}
+            let end = match pieces.peek() {
+                Some((src, dst)) if *src.start() <= start => {
+                    let end = min(*r.end(), *src.end());
+                    let to = *dst + (start - *src.start());
+                    out.push(to..=to + (end - start));
+                    end
+                }
+                // values before the next piece stay where they are. That
+                // piece starts after `start`, so there is a value before it.
+                next => {
+                    let end = next
+                        .and_then(|(src, _)| src.start().pred())
+                        .map_or(*r.end(), |before| min(*r.end(), before));
+                    out.push(start..=end);
+                    end
+                }
+            };
+            match end.succ() {
+                Some(next) if next <= *r.end() => start = next,
+                _ => break,
+            }
+        }
+    }
+}
+
+/// Whether `b`, which doesn't start before `a`, overlaps or touches it
+fn joins<T: Discrete>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> bool {
+    a.end().succ().is_none_or(|next| *b.start() <= next)
+}
+
+/// Sort the ranges and merge them in place, in a single pass after sorting.
+/// Empty ranges are dropped.
+fn coalesce<T: Discrete>(ranges: &mut Vec<RangeInclusive<T>>) {
+    ranges.retain(|r| !r.is_empty());
+    ranges.sort_unstable_by_key(|r| *r.start());
+
+    // `len` ranges at the front are done, the last of those can still grow
+    let mut len = 0;
+    for i in 0..ranges.len() {
+        let r = ranges[i].clone();
+        if len > 0 && joins(&ranges[len - 1], &r) {
+            let last = &ranges[len - 1];
+            ranges[len - 1] = *last.start()..=max(*last.end(), *r.end());
+        } else {
+            ranges[len] = r;
+            len += 1;
+        }
+    }
+    ranges.truncate(len);
+}
+
+/// Collects ranges in any order, and merges the ones that overlap or touch.
+///
+/// Ranges are merged whenever the buffer has doubled since the last merge,
+/// so it never holds more than twice as many ranges as there are after
+/// merging, however many get pushed.
+///
+/// The buffer stays around when it's cleared, so a single coalescer can be
+/// used over and over (say, once per category) without allocating again.
+#[derive(Debug, Clone)]
+pub struct Coalescer<T> {
+    ranges: Vec<RangeInclusive<T>>,
+    /// How many ranges there were after the last merge
+    merged: usize,
+}
+
+impl<T> Default for Coalescer<T> {
+    fn default() -> Self {
+        Coalescer {
+            ranges: vec![],
+            merged: 0,
+        }
+    }
+}
+
+/// Don't bother merging fewer ranges than this
+const MIN_COALESCE: usize = 8;
+
+impl<T: Discrete> Coalescer<T> {
+    pub fn new() -> Self {
+        Coalescer::default()
+    }
+
+    pub fn push(&mut self, range: RangeInclusive<T>) {
+        if !range.is_empty() {
+            self.ranges.push(range);
+            if self.ranges.len() >= 2 * self.merged.max(MIN_COALESCE) {
+                self.merge();
+            }
+        }
+    }
+
+    fn merge(&mut self) {
+        coalesce(&mut self.ranges);
+        self.merged = self.ranges.len();
+    }
+
+    /// Forget all ranges, but keep the memory
+    pub fn clear(&mut self) {
+        self.ranges.clear();
+        self.merged = 0;
+    }
+
+    /// The ranges pushed so far, sorted and merged
+    pub fn ranges(&mut self) -> &[RangeInclusive<T>] {
+        if self.merged != self.ranges.len() {
+            self.merge();
+        }
+        &self.ranges
+    }
+
+    /// The lowest value pushed so far
+    pub fn min(&self) -> Option<T> {
+        self.ranges.iter().map(|r| *r.start()).min()
+    }
+
+    pub fn into_set(mut self) -> RangeSet<T> {
+        self.ranges();
+        RangeSet {
+            ranges: self.ranges,
+        }
+    }
+}
+
+impl<T: Discrete> Extend<RangeInclusive<T>> for Coalescer<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.push(range);
+        }
+    }
+}
+
+impl<T: Discrete> From<RangeInclusive<T>> for RangeSet<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        std::iter::once(range).collect()
+    }
+}
+
+impl<T: Discrete> FromIterator<RangeInclusive<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        RangeSet {
+            ranges: RangeSet::normalise(iter.into_iter().collect()),
+        }
+    }
+}
+
+/// Show the set like `{0..=4, 10..=11}`
+impl<T: fmt::Display> fmt::Display for RangeSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{")?;
+        for (i, r) in self.ranges.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}..={}", r.start(), r.end())?;
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init, clippy::reversed_empty_ranges)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn set(ranges: &[RangeInclusive<u32>]) -> RangeSet<u32> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_normalise() {
+        assert_eq!(
+            set(&[20..=29, 0..=9, 5..=14, 15..=15, 25..=34, 40..=39]).ranges(),
+            &[0..=15, 20..=34]
+        );
+        assert_eq!(set(&[0..=9, 0..=4]).ranges(), &[0..=9]);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[0..=9, 20..=29]);
+        let b = set(&[5..=24]);
+        assert_eq!(a.union(&b).ranges(), &[0..=29]);
+        assert_eq!(a.intersection(&b).ranges(), &[5..=9, 20..=24]);
+        assert_eq!(a.difference(&b).ranges(), &[0..=4, 25..=29]);
+        assert_eq!(b.difference(&a).ranges(), &[10..=19]);
+        assert!(a.contains(0) && a.contains(29) && !a.contains(10) && !a.contains(30));
+        assert_eq!(a.to_string(), "{0..=9, 20..=29}");
+        assert_eq!(RangeSet::<u32>::new().to_string(), "{}");
+    }
+
+    #[test]
+    fn test_map_affine() {
+        let a = set(&[0..=19]);
+        // 5..=9 moves to 100..=104, 8..=14 would move to 200..=206 but 8..=9
+        // was already taken by the first piece
+        let mapped = a.map_affine([(5..=9, 100), (8..=14, 200)]);
+        assert_eq!(mapped.ranges(), &[0..=4, 15..=19, 100..=104, 202..=206]);
+    }
+
+    #[test]
+    fn test_largest_value() {
+        let all = RangeSet::from(0..=u8::MAX);
+        let top = RangeSet::from(250..=u8::MAX);
+        assert_eq!(all.difference(&top).ranges(), &[0..=249]);
+        assert_eq!(top.difference(&all), RangeSet::new());
+        assert_eq!(top.union(&(0..=249).into()), all);
+        assert!(top.contains(u8::MAX));
+
+        // the largest value can be moved, and have others moved onto it
+        let mapped = top.map_affine([(254..=u8::MAX, 0), (0..=3, 252)]);
+        assert_eq!(mapped.ranges(), &[0..=1, 250..=253]);
+        let mapped = all.map_affine([(0..=0, u8::MAX)]);
+        assert_eq!(mapped.ranges(), &[1..=u8::MAX]);
+    }
+
+    /// Every value in a set, to compare against
+    fn values(set: &RangeSet<u32>) -> BTreeSet<u32> {
+        set.ranges().iter().flat_map(|r| r.clone()).collect()
+    }
+
+    /// Ranges of up to 19 values, some of them empty
+    fn ranges() -> impl Strategy<Value = Vec<RangeInclusive<u32>>> {
+        prop::collection::vec((1u32..100, 0u32..20).prop_map(|(s, l)| s..=s + l - 1), 0..6)
+    }
+
+    fn is_normalised(set: &RangeSet<u32>) -> bool {
+        set.ranges().iter().all(|r| r.start() <= r.end())
+            && set
+                .ranges()
+                .windows(2)
+                .all(|w| w[0].end() + 1 < *w[1].start())
+    }
+
+    #[test]
+    fn test_coalescer() {
+        let mut c = Coalescer::new();
+        c.extend([20..=29, 10..=19, 0..=4, 3..=3, 40..=39]);
+        assert_eq!(c.ranges(), &[0..=4, 10..=29]);
+        assert_eq!(c.min(), Some(0));
+
+        // pushing more after merging merges again
+        c.push(5..=9);
+        assert_eq!(c.ranges(), &[0..=29]);
+
+        c.clear();
+        assert_eq!(c.ranges(), &[] as &[RangeInclusive<u32>]);
+        c.push(7..=7);
+        assert_eq!(c.into_set(), set(&[7..=7]));
+    }
+
+    #[test]
+    fn test_coalescer_stays_bounded() {
+        // everything overlaps, so it all ends up as one range
+        let mut c = Coalescer::new();
+        for i in 0u32..100_000 {
+            c.push(i..=i + 5);
+            assert!(c.ranges.len() <= 2 * MIN_COALESCE, "{}", c.ranges.len());
+        }
+        assert!(c.ranges.capacity() <= 4 * MIN_COALESCE);
+        assert_eq!(c.ranges(), &[0..=100_004]);
+
+        // the same few ranges over and over
+        c.clear();
+        for i in 0u32..100_000 {
+            let start = (i % 20) * 10;
+            c.push(start..=start + 3);
+            assert!(c.ranges.len() <= 40, "{}", c.ranges.len());
+        }
+        assert_eq!(c.ranges().len(), 20);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_coalescer(a in ranges(), b in ranges()) {
+            // push a bit, merge, then push some more
+            let mut c = Coalescer::new();
+            c.extend(a.iter().cloned());
+            c.ranges();
+            c.extend(b.iter().cloned());
+            let merged = c.into_set();
+            prop_assert!(is_normalised(&merged));
+            prop_assert_eq!(values(&merged), &values(&set(&a)) | &values(&set(&b)));
+        }
+
+        #[test]
+        fn prop_set_operations(a in ranges(), b in ranges()) {
+            let (a, b) = (set(&a), set(&b));
+            let (va, vb) = (values(&a), values(&b));
+
+            for s in [a.union(&b), a.intersection(&b), a.difference(&b)] {
+                prop_assert!(is_normalised(&s), "{:?}", s);
+            }
+            prop_assert_eq!(values(&a.union(&b)), &va | &vb);
+            prop_assert_eq!(values(&a.intersection(&b)), &va & &vb);
+            prop_assert_eq!(values(&a.difference(&b)), &va - &vb);
+            for v in 0..130 {
+                prop_assert_eq!(a.contains(v), va.contains(&v));
+            }
+        }
+
+        #[test]
+        fn prop_map_affine(
+            a in ranges(),
+            pieces in prop::collection::vec((1u32..100, 0u32..20, 0u32..100), 0..5),
+        ) {
+            let a = set(&a);
+            let pieces = pieces
+                .into_iter()
+                .map(|(src, len, dst)| (src..=src + len - 1, dst))
+                .collect::<Vec<_>>();
+
+            // map every value on its own, the first piece that has it wins
+            let expected = values(&a)
+                .into_iter()
+                .map(|v| {
+                    pieces
+                        .iter()
+                        .find(|(src, _)| src.contains(&v))
+                        .map_or(v, |(src, dst)| dst + (v - src.start()))
+                })
+                .collect::<BTreeSet<_>>();
+
+            let mapped = a.map_affine(pieces.iter().cloned());
+            prop_assert!(is_normalised(&mapped));
+            prop_assert_eq!(values(&mapped), expected);
+        }
+    }
+}
@@ -0,0 +1,388 @@
+use std::{
+    fmt,
+    fmt::Write,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    days::{day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11},
+    input::Source,
+    Answer, ParseError, Solution, SolveError,
+};
+
+/// The number of days we have solutions for
+pub const NR_DAYS: u32 = 11;
+
+/// One of the two parts of a puzzle
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => f.pad("1"),
+            Part::Two => f.pad("2"),
+        }
+    }
+}
+
+/// The outcome of running a single part of a day
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub day: u32,
+    pub part: Part,
+    pub answer: Answer,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    pub input: Source,
+}
+
+/// Why running a day failed: either the input didn't parse, or one of the
+/// parts couldn't be solved
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RunError {
+    Parse(ParseError),
+    Solve { part: Part, error: SolveError },
+}
+
+impl RunError {
+    /// Render the error, with the offending line of the input if it's a
+    /// parse error
+    pub fn render(&self, input: &str) -> String {
+        match self {
+            RunError::Parse(e) => e.render(input),
+            RunError::Solve { .. } => self.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RunError::Parse(e) => write!(f, "{}", e),
+            RunError::Solve { part, error } => write!(f, "part {}: {}", part, error),
+        }
+    }
+}
+
+impl From<ParseError> for RunError {
+    fn from(e: ParseError) -> Self {
+        RunError::Parse(e)
+    }
+}
+
+/// Parse the input once, and solve the requested parts on it, timing both
+/// phases.
+pub fn run<S: Solution>(
+    day: u32,
+    source: &Source,
+    input: &str,
+    parts: &[Part],
+) -> Result<Vec<PartResult>, RunError> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse_time = start.elapsed();
+
+    parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = match part {
+                Part::One => S::part1(&parsed),
+                Part::Two => S::part2(&parsed),
+            }
+            .map_err(|error| RunError::Solve { part, error })?;
+            Ok(PartResult {
+                day,
+                part,
+                answer,
+                parse_time,
+                solve_time: start.elapsed(),
+                input: source.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Run the requested parts of a day on the input read from `source`, or
+/// return None if we don't have a solution for that day.
+pub fn run_day(
+    day: u32,
+    source: &Source,
+    input: &str,
+    parts: &[Part],
+) -> Option<Result<Vec<PartResult>, RunError>> {
+    let results = match day {
+        1 => run::<day01::Day01>(day, source, input, parts),
+        2 => run::<day02::Day02>(day, source, input, parts),
+        3 => run::<day03::Day03>(day, source, input, parts),
+        4 => run::<day04::Day04>(day, source, input, parts),
+        5 => run::<day05::Day05>(day, source, input, parts),
+        6 => run::<day06::Day06>(day, source, input, parts),
+        7 => run::<day07::Day07>(day, source, input, parts),
+        8 => run::<day08::Day08>(day, source, input, parts),
+        9 => run::<day09::Day09>(day, source, input, parts),
+        10 => run::<day10::Day10>(day, source, input, parts),
+        11 => run::<day11::Day11>(day, source, input, parts),
+        _ => return None,
+    };
+    Some(results)
+}
+
+/// Parse a selection of days, which can be a single day (`5`), an inclusive
+/// range of days (`3..7`) or `all`.
+pub fn parse_days(s: &str) -> Result<Vec<u32>, String> {
+    let parse_day = |d: &str| match d.trim().parse::<u32>() {
+        Ok(day) if (1..=NR_DAYS).contains(&day) => Ok(day),
+        _ => Err(format!("invalid day '{}', expected 1..{}", d, NR_DAYS)),
+    };
+
+    if s == "all" {
+        return Ok((1..=NR_DAYS).collect());
+    }
+
+    match s.split_once("..") {
+        Some((from, to)) => {
+            let from = parse_day(from)?;
+            let to = parse_day(to.trim_start_matches('='))?;
+            if from > to {
+                return Err(format!("invalid range '{}', {} is after {}", s, from, to));
+            }
+            Ok((from..=to).collect())
+        }
+        None => Ok(vec![parse_day(s)?]),
+    }
+}
+
+/// How to output the results
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!(
+                "invalid format '{}', expected json, csv or text",
+                s
+            )),
+        }
+    }
+}
+
+impl Format {
+    /// Render the results in this format. Text is meant for people, json and
+    /// csv have times in nanoseconds so they can be diffed and archived.
+    pub fn render(&self, results: &[PartResult]) -> String {
+        match self {
+            Format::Text => table(results),
+            Format::Json => json(results),
+            Format::Csv => csv(results),
+        }
+    }
+}
+
+fn table(results: &[PartResult]) -> String {
+    let mut out = format!(
+        "{:>3}  {:>4}  {:>16}  {:>12}  {:>12}\n",
+        "Day", "Part", "Answer", "Parse", "Solve"
+    );
+    for r in results {
+        writeln!(
+            out,
+            "{:>3}  {:>4}  {:>16}  {:>12}  {:>12}",
+            r.day,
+            r.part,
+            r.answer,
+            format!("{:.2?}", r.parse_time),
+            format!("{:.2?}", r.solve_time),
+        )
+        .unwrap();
+    }
+    out
+}
+
+fn json(results: &[PartResult]) -> String {
+    let mut out = "[".to_owned();
+    for (i, r) in results.iter().enumerate() {
+        write!(
+            out,
+            "{}\n  {{\"day\": {}, \"part\": {}, \"answer\": {}, \"parse_ns\": {}, \"solve_ns\": {}, \"input\": \"{}\"}}",
+            if i == 0 { "" } else { "," },
+            r.day,
+            r.part,
+            r.answer,
+            r.parse_time.as_nanos(),
+            r.solve_time.as_nanos(),
+            json_escape(&r.input.to_string()),
+        )
+        .unwrap();
+    }
+    out.push_str("\n]\n");
+    out
+}
+
+fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn csv(results: &[PartResult]) -> String {
+    let mut out = "day,part,answer,parse_ns,solve_ns,input\n".to_owned();
+    for r in results {
+        writeln!(
+            out,
+            "{},{},{},{},{},{}",
+            r.day,
+            r.part,
+            r.answer,
+            r.parse_time.as_nanos(),
+            r.solve_time.as_nanos(),
+            csv_escape(&r.input.to_string()),
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// Quote a csv field if it needs it
+fn csv_escape(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("5"), Ok(vec![5]));
+        assert_eq!(parse_days("3..7"), Ok(vec![3, 4, 5, 6, 7]));
+        assert_eq!(parse_days("3..=4"), Ok(vec![3, 4]));
+        assert_eq!(parse_days("all").unwrap().len(), NR_DAYS as usize);
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("12").is_err());
+        assert!(parse_days("7..3").is_err());
+        assert!(parse_days("five").is_err());
+    }
+
+    #[test]
+    fn test_run_day() {
+        let input = "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet";
+        let results = run_day(1, &Source::Stdin, input, &[Part::One])
+            .unwrap()
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].answer, Answer::U32(142));
+
+        assert!(run_day(25, &Source::Stdin, input, &Part::BOTH).is_none());
+    }
+
+    #[test]
+    fn test_run_day_parse_error() {
+        let err = run_day(9, &Source::Stdin, "1 2 3\n4 x 6", &Part::BOTH)
+            .unwrap()
+            .unwrap_err();
+        let RunError::Parse(err) = err else {
+            panic!("expected a parse error, got {:?}", err);
+        };
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+
+    #[test]
+    fn test_run_day_solve_error() {
+        // the digits of the races don't fit in a u64 when glued together
+        let input = "Time: 10 20 30 40 50 60 70 80 90 100\nDistance: 1 2 3 4 5 6 7 8 9 10";
+        let err = run_day(6, &Source::Stdin, input, &Part::BOTH)
+            .unwrap()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            RunError::Solve {
+                part: Part::Two,
+                ..
+            }
+        ));
+        assert_eq!(
+            err.render(input),
+            "part 2: the glued together race doesn't fit in a u64"
+        );
+    }
+
+    fn results() -> Vec<PartResult> {
+        let result = |part, answer: u64, input: &str| PartResult {
+            day: 5,
+            part,
+            answer: answer.into(),
+            parse_time: Duration::from_micros(12),
+            solve_time: Duration::from_nanos(3400),
+            input: Source::File(input.into()),
+        };
+        vec![
+            result(Part::One, 35, "input/day05.txt"),
+            result(Part::Two, 46, "my \"inputs\", 2023/day05.txt"),
+        ]
+    }
+
+    #[test]
+    fn test_format_json() {
+        assert_eq!(
+            Format::Json.render(&results()),
+            r#"[
+  {"day": 5, "part": 1, "answer": 35, "parse_ns": 12000, "solve_ns": 3400, "input": "input/day05.txt"},
+  {"day": 5, "part": 2, "answer": 46, "parse_ns": 12000, "solve_ns": 3400, "input": "my \"inputs\", 2023/day05.txt"}
+]
+"#
+        );
+        assert_eq!(Format::Json.render(&[]), "[\n]\n");
+    }
+
+    #[test]
+    fn test_format_csv() {
+        assert_eq!(
+            Format::Csv.render(&results()),
+            r#"day,part,answer,parse_ns,solve_ns,input
+5,1,35,12000,3400,input/day05.txt
+5,2,46,12000,3400,"my ""inputs"", 2023/day05.txt"
+"#
+        );
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert_eq!("text".parse(), Ok(Format::Text));
+        assert!("xml".parse::<Format>().is_err());
+    }
+}
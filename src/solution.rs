@@ -0,0 +1,112 @@
+use std::fmt;
+
+use crate::parse::ParseError;
+
+/// The answer to one part of a puzzle.
+///
+/// Every day returns whatever integer type fits its puzzle best, so we keep
+/// the original type around and only unify them here.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Answer {
+    U32(u32),
+    U64(u64),
+    I32(i32),
+    Usize(usize),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::U32(v) => fmt::Display::fmt(v, f),
+            Answer::U64(v) => fmt::Display::fmt(v, f),
+            Answer::I32(v) => fmt::Display::fmt(v, f),
+            Answer::Usize(v) => fmt::Display::fmt(v, f),
+        }
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(v: u32) -> Self {
+        Answer::U32(v)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(v: u64) -> Self {
+        Answer::U64(v)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(v: i32) -> Self {
+        Answer::I32(v)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(v: usize) -> Self {
+        Answer::Usize(v)
+    }
+}
+
+/// Why a part couldn't be solved, even though the input parsed fine. Any
+/// error can be turned into one with `?`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SolveError(pub String);
+
+impl SolveError {
+    pub fn new(reason: impl Into<String>) -> Self {
+        SolveError(reason.into())
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// SolveError isn't an Error itself, otherwise this would clash with the
+// `From<T> for T` that comes with the standard library
+impl<E: std::error::Error> From<E> for SolveError {
+    fn from(e: E) -> Self {
+        SolveError(e.to_string())
+    }
+}
+
+/// A solution for a single day of the puzzle.
+///
+/// The input is parsed once, after which both parts work on the parsed
+/// representation.
+pub trait Solution {
+    type Input;
+
+    /// Parse the puzzle input
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    /// Solve part 1 of the puzzle
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError>;
+
+    /// Solve part 2 of the puzzle
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(42u32).to_string(), "42");
+        assert_eq!(Answer::from(42u64).to_string(), "42");
+        assert_eq!(Answer::from(-42i32).to_string(), "-42");
+        assert_eq!(Answer::from(42usize).to_string(), "42");
+    }
+
+    #[test]
+    fn test_solve_error() {
+        let err: SolveError = "x".parse::<u32>().unwrap_err().into();
+        assert_eq!(err.to_string(), "invalid digit found in string");
+        assert_eq!(SolveError::new("no seeds").to_string(), "no seeds");
+    }
+}